@@ -41,10 +41,21 @@ Made with ♡ by someone tired of copy-pasting code files!",
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Seed for the randomly chosen banners and kaomoji
+    ///
+    /// The same seed always produces the same decorations,
+    /// handy for reproducible demos and terminal captures.
+    ///
+    /// Example:
+    ///   treeclip run . --seed 42
+    #[arg(long, global = true, value_name = "N", verbatim_doc_comment)]
+    pub seed: Option<u64>,
 }
 
 /// Available subcommands for TreeClip.
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // Parsed once at startup; boxing buys nothing
 pub enum Commands {
     /// Run TreeClip to extract and bundle code files
     ///
@@ -64,6 +75,41 @@ pub enum Commands {
 TIP: Create a .treeclipignore file (like .gitignore) for permanent exclusions!"
     )]
     Run(args::RunArgs),
+
+    /// Show statistics for a bundle, or for one before it exists
+    ///
+    /// Prints the same statistics box as `run --stats`. Given a
+    /// bundle file, it analyzes it without regenerating anything;
+    /// given directories, it reports how big their bundle would be
+    /// without writing a file or touching the clipboard.
+    #[command(
+        verbatim_doc_comment,
+        after_help = "QUICK EXAMPLES:
+    treeclip stats treeclip_temp.txt      # Stats for the default output
+    treeclip stats ./exports/bundle.txt   # Stats for any bundle
+    treeclip stats ./src -e target        # Stats a run would produce"
+    )]
+    Stats(args::StatsArgs),
+
+    /// Recreate the files of a bundle on disk
+    ///
+    /// The reverse of `run`: every `==> path` section becomes a file
+    /// again. Bundles made with `run --base64` round-trip byte for
+    /// byte; plain text sections get their content plus a newline.
+    #[command(
+        verbatim_doc_comment,
+        after_help = "QUICK EXAMPLES:
+    treeclip unbundle bundle.txt             # Extract into the current dir
+    treeclip unbundle bundle.txt -o restored # Extract into ./restored"
+    )]
+    Unbundle(args::UnbundleArgs),
+
+    /// Print the JSON Schema of `run --format json` output
+    ///
+    /// For tools consuming JSON bundles: describes each file
+    /// element (path, size, content) and which fields are optional.
+    #[command(hide = true, verbatim_doc_comment)]
+    JsonSchema,
 }
 
 // -------------------------------------------- Private Helper Functions --------------------------------------------
@@ -107,6 +153,7 @@ fn get_styles() -> clap::builder::Styles {
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrows_for_generic_args)] // Arg lists are passed as `&[..]` slices
 mod cli_tests {
     use super::*;
     use clap::Parser;
@@ -119,6 +166,7 @@ mod cli_tests {
             Commands::Run(args) => {
                 assert_eq!(args.input_paths, vec![PathBuf::from("test_dir")]);
             }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -132,6 +180,7 @@ mod cli_tests {
                 assert_eq!(args.input_paths[1], PathBuf::from("dir2"));
                 assert_eq!(args.input_paths[2], PathBuf::from("dir3"));
             }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -152,6 +201,7 @@ mod cli_tests {
                 assert_eq!(args.exclude, vec!["node_modules", ".git"]);
                 assert_eq!(args.input_paths, vec![PathBuf::from(".")]);
             }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -172,6 +222,7 @@ mod cli_tests {
                 assert!(args.editor);
                 assert!(args.verbose);
             }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -183,9 +234,46 @@ mod cli_tests {
             Commands::Run(args) => {
                 assert!(args.fast_mode);
             }
+            _ => panic!("expected the run command"),
         }
     }
 
+    #[test]
+    fn test_cli_parse_stats_command() {
+        let cli = Cli::parse_from(["treeclip", "stats", "bundle.txt"]);
+        match cli.command {
+            Commands::Stats(args) => assert_eq!(args.paths, vec![PathBuf::from("bundle.txt")]),
+            _ => panic!("expected the stats command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_unbundle_command() {
+        let cli = Cli::parse_from(["treeclip", "unbundle", "bundle.txt", "-o", "out"]);
+        match cli.command {
+            Commands::Unbundle(args) => {
+                assert_eq!(args.bundle, PathBuf::from("bundle.txt"));
+                assert_eq!(args.output_dir, PathBuf::from("out"));
+            }
+            _ => panic!("expected the unbundle command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_json_schema_command() {
+        let cli = Cli::parse_from(["treeclip", "json-schema"]);
+        assert!(matches!(cli.command, Commands::JsonSchema));
+    }
+
+    #[test]
+    fn test_cli_parse_global_seed() {
+        let cli = Cli::parse_from(["treeclip", "run", ".", "--seed", "42"]);
+        assert_eq!(cli.seed, Some(42));
+
+        let cli = Cli::parse_from(["treeclip", "--seed", "7", "stats", "bundle.txt"]);
+        assert_eq!(cli.seed, Some(7));
+    }
+
     #[test]
     fn test_cli_requires_subcommand() {
         let result = Cli::try_parse_from(&["treeclip"]);
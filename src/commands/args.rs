@@ -1,104 +1,1311 @@
 //! args - Defines command-line arguments and their validation logic.
 
-use clap::{ArgAction, ValueHint};
+use crate::core::clipboard::{ClipboardBackend, ClipboardFormat};
+use crate::core::traversal::limiter;
+use crate::core::ui::progress::ProgressStyle;
+use crate::core::utils;
+use clap::{ArgAction, ValueEnum, ValueHint};
+use regex::Regex;
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Output formats supported by the bundle writer.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Plain text with `==> path` headers (default)
+    #[default]
+    Text,
+    /// The whole text bundle as one escaped JSON string: {"bundle":"..."}
+    JsonString,
+    /// A Markdown heading per file with its content in a language-tagged code fence
+    Markdown,
+    /// A JSON array with one {"path","bytes","content"} object per file
+    Json,
+}
+
+/// Encodings for binary files force-included with `--include-binary`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BinaryEncoding {
+    /// Standard base64 (default)
+    #[default]
+    Base64,
+    /// Lowercase hexadecimal
+    Hex,
+}
+
+/// Orders in which files are written to the bundle.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Alphabetically by path (default)
+    #[default]
+    Path,
+    /// Smallest files first; files of equal size by path
+    Size,
+    /// Least recently modified first; files modified at the same time by path
+    Mtime,
+    /// Filesystem walk order, which may differ between runs and machines
+    None,
+}
+
+/// Formats of the `--events` stream.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventFormat {
+    /// Newline-delimited JSON, one event object per line
+    Ndjson,
+}
+
+/// How `--stats` counts words.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WordMode {
+    /// Runs of non-whitespace characters, like `wc -w` (default)
+    #[default]
+    Whitespace,
+    /// Runs of letters and digits; punctuation separates words
+    Alnum,
+}
+
+impl WordMode {
+    /// Checks whether `ch` is part of a word in this mode.
+    pub fn is_word_char(self, ch: char) -> bool {
+        match self {
+            WordMode::Whitespace => !ch.is_whitespace(),
+            WordMode::Alnum => ch.is_alphanumeric(),
+        }
+    }
+}
 
 /// Arguments for the `run` command.
-#[derive(clap::Args)]
+#[derive(clap::Args, Clone, Default)]
 pub struct RunArgs {
     /// Paths to traverse (defaults to current directory)
     ///
-    /// Specify which directories to scan and extract files from.
-    /// Use '.' for current directory or provide any valid paths.
-    /// Multiple paths can be specified.
+    /// Specify which directories to scan and extract files from.
+    /// Use '.' for current directory or provide any valid paths.
+    /// Multiple paths can be specified.
+    ///
+    /// Examples:
+    ///   treeclip run .
+    ///   treeclip run ./src
+    ///   treeclip run ~/projects/my-app
+    ///   treeclip run . src some/other/input/path
+    #[arg(
+        default_value = ".",
+        value_parser = validate_path,
+        value_hint = ValueHint::DirPath,
+        verbatim_doc_comment,
+        num_args = 1..,
+    )]
+    pub input_paths: Vec<PathBuf>,
+
+    /// Output file path for the extracted content
+    ///
+    /// Where to save the bundled output. If not specified,
+    /// creates 'treeclip_temp.txt' in the current directory.
+    ///
+    /// Pass '-' to write to stdout, same as --stdout.
+    ///
+    /// Examples:
+    ///   -o output.txt
+    ///   --output-path ./exports/bundle.txt
+    ///   -o - | less
+    #[arg(
+        short,
+        long,
+        default_value = ".",
+        value_parser = validate_path,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        verbatim_doc_comment
+    )]
+    pub output_path: Option<PathBuf>,
+
+    /// Write the bundle to stdout instead of an output file
+    ///
+    /// Banners and animations are skipped (as with --fast-mode)
+    /// and logs are routed to stderr, so stdout carries only the
+    /// bundle and can be piped safely. Files stream out as they
+    /// are read, unless an option needs the finished bundle first
+    /// (--stats, --clipboard, --highlight, ...).
+    /// Equivalent to '-o -'.
+    ///
+    /// Examples:
+    ///   treeclip run . --stdout > bundle.txt
+    ///   treeclip run src --stdout --fast-mode | wc -l
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "output_path",
+        verbatim_doc_comment
+    )]
+    pub stdout: bool,
+
+    /// Flush the output after every file
+    ///
+    /// Lets a consumer reading the bundle as it's written (e.g. through
+    /// --stdout) see each file as soon as it's bundled, instead of in
+    /// large buffered chunks.
+    /// Note: flushing this often costs throughput on large bundles.
+    ///
+    /// Example:
+    ///   treeclip run . --stdout --flush-per-file | less
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub flush_per_file: bool,
+
+    /// Write one bundle per top-level directory of the input
+    ///
+    /// For monorepos: files are grouped by their first directory under
+    /// the input and each group is written to <group>.txt. Files
+    /// directly under the input go to root.txt, so they can't sit
+    /// next to a directory named 'root'. --output-path names the
+    /// directory holding the bundles (default: ./treeclip_split); with
+    /// several inputs, each input gets its own subdirectory.
+    ///
+    /// Example:
+    ///   treeclip run ./packages --split-by-dir -o ./bundles
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["stdout", "clipboard", "editor", "stats", "title", "format"],
+        verbatim_doc_comment
+    )]
+    pub split_by_dir: bool,
+
+    /// Write a JSON index of where each file sits in the bundle
+    ///
+    /// The sidecar maps each file's relative path to the byte offset
+    /// and length of its section ('==> path' header through content),
+    /// so tools can seek straight to one file without parsing the
+    /// whole bundle. Not available with --format json-string.
+    ///
+    /// Example:
+    ///   -o bundle.txt --index-out bundle.index.json
+    ///   → [{"path": "src/main.rs", "offset": 0, "length": 42}, ...]
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["split_by_dir", "split_tokens"],
+        verbatim_doc_comment
+    )]
+    pub index_out: Option<PathBuf>,
+
+    /// Stream a machine-readable event per bundled file
+    ///
+    /// Writes one JSON object per line as each file is bundled,
+    /// then a final summary, so integrations can follow a run live.
+    /// Events go to stderr, or to --events-out.
+    ///
+    /// Example:
+    ///   --events ndjson
+    ///   → {"event":"file","path":"src/main.rs","bytes":120}
+    ///   → {"event":"done","count":1}
+    #[arg(long, value_name = "FORMAT", value_enum, verbatim_doc_comment)]
+    pub events: Option<EventFormat>,
+
+    /// Write --events to this file instead of stderr
+    ///
+    /// Keeps events apart from log output. Any writable path works,
+    /// including another file descriptor on Unix.
+    ///
+    /// Example:
+    ///   --events ndjson --events-out /dev/fd/3 3>events.ndjson
+    #[arg(long, value_name = "FILE", requires = "events", verbatim_doc_comment)]
+    pub events_out: Option<PathBuf>,
+
+    /// Syntax-highlight the bundle when it goes to a terminal
+    ///
+    /// Colors each file's content by language for eyeballing the
+    /// bundle in the terminal. Only applies to --stdout (or -o -)
+    /// when stdout is a TTY: bundles written to files, pipes, or
+    /// the clipboard never contain escape codes.
+    ///
+    /// Example:
+    ///   treeclip run src --stdout --highlight
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub highlight: bool,
+
+    /// Add a short content hash to the output file name
+    ///
+    /// The bundle is written to a temp file, then renamed with the
+    /// first 8 hex digits of its SHA-256 before the extension, for
+    /// content-addressable archiving. Identical bundles get identical
+    /// names.
+    ///
+    /// Example:
+    ///   -o bundle.txt --hash-suffix  →  bundle-1f3a9c0e.txt
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["stdout", "split_by_dir", "split_tokens"],
+        verbatim_doc_comment
+    )]
+    pub hash_suffix: bool,
+
+    /// Refuse to write through an output path that is a symlink
+    ///
+    /// By default a symlinked output is followed: the bundle
+    /// overwrites the file the link points to, like shell
+    /// redirection does. With this flag treeclip errors out
+    /// instead, so a stray link can't clobber another file.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub no_follow_output_symlink: bool,
+
+    /// Print only the absolute output path on stdout
+    ///
+    /// Everything else (banners, logs, stats) goes to stderr, so
+    /// scripts can capture where the bundle landed. Split output
+    /// has no single path to print.
+    ///
+    /// Example:
+    ///   OUT=$(treeclip run . --print-output-path)
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["stdout", "delete", "split_tokens", "split_by_dir"],
+        verbatim_doc_comment
+    )]
+    pub print_output_path: bool,
+
+    /// Split the bundle into parts of at most N estimated tokens
+    ///
+    /// Writes <name>.part1.txt, <name>.part2.txt, ... next to the
+    /// output path, starting a new part whenever the next file would
+    /// exceed the budget. A file is never split across parts, so one
+    /// larger than N gets a part of its own.
+    /// Tokens are estimated at ~4 characters each.
+    ///
+    /// Example:
+    ///   treeclip run . --split-tokens 100000 -o bundle.txt
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = validate_positive,
+        conflicts_with_all = ["stdout", "clipboard", "editor", "stats", "title", "format", "split_by_dir"],
+        verbatim_doc_comment
+    )]
+    pub split_tokens: Option<usize>,
+
+    /// Root directory for .treeclipignore file lookup
+    ///
+    /// Specifies where to search for the .treeclipignore file.
+    /// Usually you don't need to change this.
+    #[arg(
+        long,
+        default_value = ".",
+        value_parser = validate_path,
+        value_name = "DIR",
+        value_hint = ValueHint::DirPath,
+        hide = true  // Hide from help unless --help is used
+    )]
+    pub root: Option<PathBuf>,
+
+    /// Fail if a bundled file lies outside the root directory
+    ///
+    /// File headers are shown relative to --root. Files outside it
+    /// fall back to their full path, which usually means --root is
+    /// misconfigured. By default this only prints a warning.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub strict_root: bool,
+
+    /// Fail on unreadable directories instead of skipping them
+    ///
+    /// By default a directory that can't be read (permission
+    /// denied) is skipped with a warning and traversal continues.
+    /// With --strict the whole run fails instead.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub strict: bool,
+
+    /// Drop inputs that lie inside another input
+    ///
+    /// Overlapping inputs (e.g. '.' and './src') would bundle the
+    /// same files twice; treeclip always warns about them. With this
+    /// flag the contained inputs are dropped instead.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub dedup_inputs: bool,
+
+    /// Succeed with an empty bundle when no files are found
+    ///
+    /// By default a run whose inputs hold no files to bundle
+    /// fails. With this flag it writes an empty output (or only
+    /// the --title header) and exits successfully, which suits
+    /// automation where an empty selection is normal.
+    ///
+    /// Example:
+    ///   treeclip run ./generated --allow-empty -o bundle.txt
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub allow_empty: bool,
+
+    /// Add to the output file instead of replacing it
+    ///
+    /// The new files follow the existing content after a blank line,
+    /// so several runs over unrelated directories accumulate into
+    /// one bundle. Finding no files isn't an error as long as the
+    /// output already has content.
+    ///
+    /// Example:
+    ///   treeclip run ./api -o bundle.txt
+    ///   treeclip run ./web -o bundle.txt --append
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["stdout", "split_by_dir", "split_tokens", "hash_suffix"],
+        verbatim_doc_comment
+    )]
+    pub append: bool,
+
+    /// Descend into symlinked directories
+    ///
+    /// By default symlinked directories are not walked (symlinked
+    /// files are still bundled). A directory reached again through a
+    /// link, e.g. a link back to its parent, is skipped with a
+    /// warning, so cycles can't hang the walk or duplicate files.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub follow_symlinks: bool,
+
+    /// Descend at most N directory levels below each input
+    ///
+    /// Depth 1 bundles only the files directly inside an input,
+    /// depth 2 also those one directory down, and so on. Depth is
+    /// counted from each input path separately, so with
+    /// 'treeclip run . src --max-depth 1' the top-level files of
+    /// both '.' and 'src' are bundled.
+    ///
+    /// Example:
+    ///   treeclip run --max-depth 2
+    #[arg(long, value_name = "N", value_parser = validate_positive, verbatim_doc_comment)]
+    pub max_depth: Option<usize>,
+
+    /// Read the paths to bundle from the clipboard
+    ///
+    /// Copy a list of paths (one per line, e.g. from an issue
+    /// tracker) and bundle exactly those. Every path must exist.
+    /// Replaces the positional INPUT_PATHS.
+    ///
+    /// Example:
+    ///   treeclip run --input-from-clipboard --stdout
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "input_paths",
+        verbatim_doc_comment
+    )]
+    pub input_from_clipboard: bool,
+
+    /// Read more paths to bundle from a file, or from stdin with '-'
+    ///
+    /// One path per line; blank lines are ignored and every path
+    /// must exist. The paths are added to INPUT_PATHS; when none
+    /// are given (or just '.'), only the listed paths are bundled.
+    ///
+    /// Examples:
+    ///   find . -name '*.rs' | treeclip run --paths-from -
+    ///   treeclip run --paths-from files.txt
+    #[arg(
+        long,
+        value_name = "FILE|-",
+        value_hint = ValueHint::FilePath,
+        conflicts_with = "input_from_clipboard",
+        verbatim_doc_comment
+    )]
+    pub paths_from: Option<PathBuf>,
+
+    /// Only collect files matching these glob patterns
+    ///
+    /// An allowlist counterpart to --exclude: when given, a file is
+    /// bundled only if it matches at least one --include pattern
+    /// and no exclusion. Directories are always searched, so '*.rs'
+    /// finds Rust files at any depth. Can be specified multiple times.
+    ///
+    /// Examples:
+    ///   --include '*.rs' --include '*.toml'
+    ///   --include 'src/'     (only files under src)
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    pub include: Vec<String>,
+
+    /// Order files by the first --include pattern they match
+    ///
+    /// Files matching the first pattern come first, then those
+    /// matching the second, and so on; within each group --sort
+    /// decides as usual.
+    ///
+    /// Example (schema before code):
+    ///   --include '*.sql' --include '*.rs' --order-by-include
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "include",
+        verbatim_doc_comment
+    )]
+    pub order_by_include: bool,
+
+    /// Only collect files whose content matches this regex
+    ///
+    /// Files without a match are left out, like excluded ones.
+    /// Binary and non-UTF-8 files never match. Combine with
+    /// --match-context to bundle just the matching regions.
+    ///
+    /// Example:
+    ///   --include-matching 'fn parse_\w+'
+    #[arg(long, value_name = "REGEX", value_parser = Regex::new, verbatim_doc_comment)]
+    pub include_matching: Option<Regex>,
+
+    /// Bundle only matching lines plus N lines of context
+    ///
+    /// For files selected by --include-matching, emits just the
+    /// matching lines and N lines above and below each (like
+    /// 'grep -C N'), with a '...' line between separate regions.
+    ///
+    /// Example:
+    ///   --include-matching TODO --match-context 3
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "include_matching",
+        verbatim_doc_comment
+    )]
+    pub match_context: Option<usize>,
+
+    /// Exclude files/folders matching these glob patterns
+    ///
+    /// Can be specified multiple times. Supports glob patterns
+    /// like '*.log', 'node_modules', 'target/**', etc.
+    ///
+    /// Common patterns:
+    ///   -e node_modules      (exclude directory)
+    ///   -e '*.log'           (exclude all .log files)
+    ///   -e 'target'          (exclude Rust build dir)
+    ///   -e '__pycache__'     (exclude Python cache)
+    ///
+    /// Scope a pattern to a single input with an '<input>:' prefix:
+    ///   -e 'frontend:*.test.ts'  (only while traversing 'frontend')
+    ///
+    /// Tip: Use .treeclipignore file for permanent exclusions!
+    #[arg(
+        short,
+        long,
+        value_name = "PATTERN",
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    pub exclude: Vec<String>,
+
+    /// Make --exclude patterns win over ignore files
+    ///
+    /// CLI patterns already come after .treeclipignore and .ignore,
+    /// but a directory excluded by a file is skipped whole, so a
+    /// '!dir/file' re-inclusion can't reach into it. With this flag
+    /// CLI patterns are checked first and decide outright, and a
+    /// directory a re-inclusion spells out is still entered.
+    ///
+    /// Example (.treeclipignore contains 'build/'):
+    ///   -e '!build/config.json' --cli-patterns-override
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub cli_patterns_override: bool,
+
+    /// Only bundle files that git considers text
+    ///
+    /// Inside a git repository, asks git which files are text,
+    /// honoring .gitattributes ('-text', 'binary') and skipping
+    /// binaries and Git LFS pointers. Untracked files are judged
+    /// by their content; git-ignored files are left out.
+    ///
+    /// Outside a git repository this flag has no effect.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub text_only_by_git: bool,
+
+    /// Annotate each header with the file's last commit
+    ///
+    /// Inside a git repository, appends '(last: <author>, <date>)'
+    /// to every file header, from the last commit touching the
+    /// file. Untracked files get no annotation. A single 'git log'
+    /// covers the whole input.
+    ///
+    /// Outside a git repository this flag has no effect.
+    ///
+    /// Example:
+    ///   ==> src/main.rs (last: Jane Doe, 2024-05-01)
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub git_meta: bool,
+
+    /// Annotate each header with the file's estimated tokens
+    ///
+    /// Appends '(~<tokens> tokens)' to every file header, using
+    /// the same estimate as --stats, to spot the files worth
+    /// excluding when a bundle runs over a context window.
+    ///
+    /// Example:
+    ///   ==> src/main.rs (~420 tokens)
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub per_file_tokens: bool,
+
+    /// Report how often each exclusion pattern matched
+    ///
+    /// After the run, lists every pattern from .treeclipignore,
+    /// .ignore, and --exclude with its match count, flagging
+    /// patterns that never matched as "unused" so you can prune them.
+    ///
+    /// Note: only the pattern that decides a path is credited, so a
+    /// pattern always overridden by a later one also shows as unused.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub pattern_stats: bool,
+
+    /// Show a histogram of the bundled file sizes
+    ///
+    /// Counts files in the buckets < 1 KB, 1-10 KB, 10-100 KB, and
+    /// > 100 KB to show what the bundle is made of.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub size_histogram: bool,
+
+    /// Show how many bundled files are in each language
+    ///
+    /// Files are grouped by extension, the same way code fences are
+    /// labelled; unknown extensions count as "other".
+    ///
+    /// Example:
+    ///   treeclip run --language-report
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub language_report: bool,
+
+    /// Print the resolved plan before bundling
+    ///
+    /// Summarizes what the run will do: the inputs, every effective
+    /// exclusion pattern and where it came from (.treeclipignore,
+    /// .ignore, --exclude), the output destination, the format, and
+    /// an estimated file count from a quick metadata-only pass.
+    /// Bundling then proceeds as usual.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub explain: bool,
+
+    /// Print the files that would be bundled as JSON, then stop
+    ///
+    /// Runs the same filters as a real run but writes no bundle,
+    /// touches no clipboard, and opens no editor. Prints one JSON
+    /// object to stdout (all other output goes to stderr):
+    ///   {"files":[{"path":"src/main.rs","bytes":120}],
+    ///    "file_count":1,"total_bytes":120}
+    ///
+    /// Example:
+    ///   treeclip run src --dry-run-json | jq '.files[].path'
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub dry_run_json: bool,
+
+    /// List the files that would be bundled, then stop
+    ///
+    /// The human-readable --dry-run-json: runs the same filters as
+    /// a real run and prints each relative path to stdout, then the
+    /// file count and estimated size to stderr. No bundle is written,
+    /// the clipboard is left alone, and no editor opens. With
+    /// --format json the list is the --dry-run-json object.
+    ///
+    /// Example:
+    ///   treeclip run src -e target --dry-run
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "dry_run_json",
+        verbatim_doc_comment
+    )]
+    pub dry_run: bool,
+
+    /// Exclude common lockfiles
+    ///
+    /// Lockfiles are huge and rarely useful as AI context.
+    /// Excludes: Cargo.lock, package-lock.json, npm-shrinkwrap.json,
+    /// yarn.lock, pnpm-lock.yaml, bun.lockb, poetry.lock,
+    /// Pipfile.lock, uv.lock, Gemfile.lock, composer.lock, go.sum,
+    /// mix.lock, pubspec.lock, Podfile.lock, flake.lock
+    ///
+    /// Re-include one with e.g. --exclude '!Cargo.lock'.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub skip_lockfiles: bool,
+
+    /// Skip files with these extensions, without reading them
+    ///
+    /// Comma-separated, case-insensitive, with or without the dot.
+    /// Checked by name alone, so known binary types (images,
+    /// archives, PDFs) are left out before any read. Multi-part
+    /// extensions like 'tar.gz' work; 'logo.png.txt' is not a png.
+    ///
+    /// Example:
+    ///   treeclip run --skip-ext png,jpg,zip,pdf
+    #[arg(
+        long,
+        value_name = "EXTS",
+        value_parser = parse_extensions,
+        default_value = "",
+        hide_default_value = true,
+        verbatim_doc_comment
+    )]
+    pub skip_ext: HashSet<String>,
+
+    /// Bundle matching files as "context only" stubs
+    ///
+    /// Matching files keep their '==> path' header, but their
+    /// content is replaced by '(content omitted, N bytes)'. The AI
+    /// still sees the structure without spending tokens on it.
+    /// With --format json they get "stub":true and no content.
+    /// Can be specified multiple times; same glob syntax as --exclude.
+    ///
+    /// Examples:
+    ///   --stub 'vendor/'       (stub a whole directory)
+    ///   --stub '*.min.js'      (stub minified bundles)
+    #[arg(
+        long,
+        value_name = "GLOB",
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    pub stub: Vec<String>,
+
+    /// Merge all files with this extension into one section
+    ///
+    /// Handy for many tiny config fragments: instead of one header
+    /// per file, they share a single '=== *.EXT ===' section placed
+    /// after the other files, each introduced by a '-- path' line.
+    /// Can be specified multiple times; multi-part extensions work.
+    ///
+    /// Examples:
+    ///   --merge-ext env.d
+    ///   --merge-ext yaml --merge-ext toml
+    #[arg(
+        long,
+        value_name = "EXT",
+        action = ArgAction::Append,
+        conflicts_with = "as_patch",
+        verbatim_doc_comment
+    )]
+    pub merge_ext: Vec<String>,
+
+    /// Order in which files are written to the bundle
+    ///
+    /// Orders:
+    ///   path  alphabetically by path (default); two dumps of the
+    ///         same tree diff cleanly, whatever machine made them
+    ///   size  smallest files first, ties by path
+    ///   mtime least recently modified first, ties by path
+    ///   none  whatever order the filesystem lists them in
+    /// The --tree overview lists the files in the same order.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = SortOrder::Path,
+        value_name = "ORDER",
+        verbatim_doc_comment
+    )]
+    pub sort: SortOrder,
+
+    /// Reverse the --sort order
+    ///
+    /// Example (largest files first):
+    ///   --sort size --reverse
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub reverse: bool,
+
+    /// Bundle the most recently modified files first
+    ///
+    /// Shortcut for '--sort mtime --reverse'. Combine with
+    /// --max-files for a "what I worked on lately" bundle.
+    /// Several inputs are each sorted on their own and follow
+    /// in the order given, so --max-files fills up from the
+    /// first input.
+    ///
+    /// Example:
+    ///   treeclip run . --recent --max-files 10
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["sort", "reverse"],
+        verbatim_doc_comment
+    )]
+    pub recent: bool,
+
+    /// Only bundle files last modified before this date
+    ///
+    /// An ISO date, optionally with a time, read as UTC:
+    /// '2024-03-01', '2024/3/1', '20240301', '2024-03-01T12:30'.
+    /// Combine with --modified-after for a date range.
+    ///
+    /// Example:
+    ///   treeclip run --modified-before 2024-01-01
+    #[arg(long, value_name = "DATE", value_parser = parse_date, verbatim_doc_comment)]
+    pub modified_before: Option<SystemTime>,
+
+    /// Only bundle files last modified on or after this date
+    ///
+    /// Accepts the same dates as --modified-before; a date alone
+    /// means midnight UTC, so the whole day is included.
+    ///
+    /// Example:
+    ///   treeclip run --modified-after 2024-06-01
+    #[arg(long, value_name = "DATE", value_parser = parse_date, verbatim_doc_comment)]
+    pub modified_after: Option<SystemTime>,
+
+    /// Bundle at most this many files
+    ///
+    /// Keeps the first files in sort order, after every other
+    /// filter. Across several inputs the limit is shared and
+    /// used up input by input, in the order given.
+    ///
+    /// Example:
+    ///   --max-files 20
+    #[arg(long, value_name = "N", value_parser = validate_positive, verbatim_doc_comment)]
+    pub max_files: Option<usize>,
+
+    /// Start the bundle with a tree of the bundled files
+    ///
+    /// Prepends an ASCII overview of the project structure, like
+    /// the 'tree' command, listing exactly the files whose content
+    /// follows (exclusions and filters applied). Several inputs
+    /// share one tree; each --split-by-dir bundle gets its own.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub tree: bool,
+
+    /// Also show empty directories in the --tree overview
+    ///
+    /// Handy when discussing a project's scaffolding. Only
+    /// directories with nothing in them count; excluded or hidden
+    /// ones stay out. They're marked with a trailing '/'.
+    ///
+    /// Example:
+    ///   treeclip run . --tree --tree-show-empty-dirs
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub tree_show_empty_dirs: bool,
+
+    /// Format each file as a pseudo-patch adding it
+    ///
+    /// Headers become '+++ path' (after '--- /dev/null' and an
+    /// '@@' hunk line) and every content line is prefixed with '+',
+    /// so diff-based review tools and Markdown diff highlighters
+    /// render the bundle as one big addition.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub as_patch: bool,
+
+    /// Also honor ripgrep-style .ignore files
+    ///
+    /// Loads the '.ignore' file from the root alongside
+    /// .treeclipignore, so existing ripgrep ignores apply for free.
+    ///
+    /// Precedence (later wins, '!pattern' re-includes):
+    ///   .ignore  <  .treeclipignore  <  --exclude
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub use_dot_ignore: bool,
+
+    /// Also honor .gitignore files
+    ///
+    /// Loads the root's '.gitignore', plus those of its parent
+    /// directories up to the repository's top level, so rules
+    /// already kept for git don't need repeating in .treeclipignore.
+    ///
+    /// Precedence (later wins, '!pattern' re-includes):
+    ///   .gitignore  <  .ignore  <  .treeclipignore  <  --exclude
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub use_gitignore: bool,
+
+    /// Only read .treeclipignore from the root, not its parents
+    ///
+    /// By default the .treeclipignore files of the root's parent
+    /// directories apply too (nearer ones winning), up to the
+    /// repository's top level, so a run from a subdirectory still
+    /// honors the project's rules. The home directory is never read.
+    ///
+    /// Example:
+    ///   cd crates/app && treeclip run --no-upward-ignore
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub no_upward_ignore: bool,
+
+    /// Title written at the very top of the bundle
+    ///
+    /// Handy for labeling bundles when sharing several of them
+    /// with an AI. The header includes the generation date
+    /// unless --stable-timestamps is set. With --format markdown
+    /// the title becomes a '# ' heading.
+    ///
+    /// Example:
+    ///   --title "Backend API"  →  === Backend API ===
+    #[arg(long, value_name = "STRING", verbatim_doc_comment)]
+    pub title: Option<String>,
+
+    /// Omit generation timestamps for reproducible output
+    ///
+    /// Running twice on the same tree produces identical bundles,
+    /// which keeps diffs and caches stable.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub stable_timestamps: bool,
+
+    /// Produce byte-identical bundles on every OS
+    ///
+    /// A preset for reproducible output; given the same file
+    /// contents, Linux, macOS and Windows write the same bytes:
+    ///   - paths in headers use '/' separators
+    ///   - files are sorted by path (--sort path, not reversed)
+    ///   - CRLF line endings in content become LF
+    ///   - generation timestamps are omitted (--stable-timestamps)
+    ///
+    /// The output format is whatever --format selects.
+    ///
+    /// Example:
+    ///   treeclip run . --canonical -o bundle.txt
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["sort", "reverse", "recent"],
+        verbatim_doc_comment
+    )]
+    pub canonical: bool,
+
+    /// Format of the bundled output
+    ///
+    /// Formats:
+    ///   text         '==> path' headers followed by raw content (default)
+    ///   json-string  the text bundle as a single JSON string value,
+    ///                e.g. {"bundle":"==> src/main.rs\n..."}
+    ///   markdown     a '## `path`' heading per file, content in a code
+    ///                fence tagged with its language (```rust for .rs)
+    ///   json         an array with one object per file, e.g.
+    ///                [{"path":"src/main.rs","bytes":120,"content":"..."}];
+    ///                binary files get "binary":true and --stub files
+    ///                "stub":true, with no content
+    ///
+    /// json-string is handy for embedding a bundle in an API request body;
+    /// markdown pastes cleanly into chat assistants; json suits scripts
+    /// post-processing the files.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        value_name = "FORMAT",
+        verbatim_doc_comment
+    )]
+    pub format: OutputFormat,
+
+    /// Template for the line above each file [default: "==> {path}"]
+    ///
+    /// {path} is replaced with the file's path relative to the root.
+    /// Applies to the text format (markdown has its own headings).
+    /// Note: 'treeclip unbundle' only understands the default header.
+    ///
+    /// Examples:
+    ///   --header-format "// File: {path}"
+    ///   --header-format "### {path}"
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        value_parser = parse_header_format,
+        verbatim_doc_comment
+    )]
+    pub header_format: Option<String>,
+
+    /// Don't end the output with a newline
+    ///
+    /// Trims the single trailing newline after the last file, for
+    /// tools that are picky about how a file ends.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["split_by_dir", "split_tokens"],
+        verbatim_doc_comment
+    )]
+    pub no_final_newline: bool,
+
+    /// Copy the output to system clipboard
+    ///
+    /// After extraction, automatically copies the entire
+    /// output to your clipboard for easy pasting into AI chats.
+    ///
+    /// Platform notes:
+    ///   • Windows/macOS: Works out of the box
+    ///   • Linux: Requires xclip or wl-clipboard
+    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
+    pub clipboard: bool,
+
+    /// MIME type hint used when copying to the clipboard
+    ///
+    /// Some applications only accept specific clipboard formats.
+    /// Only honored together with --clipboard.
+    ///
+    /// Supported hints:
+    ///   text/plain   (default, works everywhere)
+    ///   text/html    (content is wrapped in an escaped <pre> block)
+    ///
+    /// Any other type (e.g. text/markdown) cannot be set through the
+    /// system clipboard APIs and falls back to text/plain with a notice.
+    #[arg(long, value_name = "MIME", verbatim_doc_comment)]
+    pub clipboard_mime: Option<String>,
+
+    /// Representation placed on the clipboard
+    ///
+    /// Formats:
+    ///   text  plain text (default)
+    ///   html  HTML for pasting into rich editors, with the plain
+    ///         text as a fallback; a --format markdown bundle is
+    ///         rendered (headings, code blocks), anything else is
+    ///         wrapped in a <pre> block
+    ///
+    /// Only honored together with --clipboard and the system backend.
+    ///
+    /// Example:
+    ///   --format markdown --clipboard --clipboard-format html
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = ClipboardFormat::Text,
+        conflicts_with = "clipboard_mime",
+        verbatim_doc_comment
+    )]
+    pub clipboard_format: ClipboardFormat,
+
+    /// How long to wait after setting the clipboard, in milliseconds
+    ///
+    /// Some platforms only keep the clipboard content if treeclip
+    /// holds on briefly after setting it; the default is 100 ms.
+    /// Scripts running many copies back to back can lower it, or
+    /// use 0 when the clipboard isn't pasted from right away.
+    /// Applies to --stats-to-clipboard too.
+    ///
+    /// Example:
+    ///   treeclip run --clipboard --clipboard-wait-ms 0
+    #[arg(long, value_name = "MS", verbatim_doc_comment)]
+    pub clipboard_wait_ms: Option<u64>,
+
+    /// How the output is placed on the clipboard
+    ///
+    /// Backends:
+    ///   system  the OS clipboard (default)
+    ///   osc52   ask the terminal via the OSC 52 escape sequence;
+    ///           works over SSH and in containers, if the terminal
+    ///           supports it (kitty, iTerm2, WezTerm, tmux, ...)
+    #[arg(
+        long,
+        value_enum,
+        value_name = "BACKEND",
+        default_value_t = ClipboardBackend::System,
+        verbatim_doc_comment
+    )]
+    pub clipboard_backend: ClipboardBackend,
+
+    /// Gzip the OSC 52 clipboard payload to fit terminal limits
+    ///
+    /// Large bundles can exceed the escape-sequence size a terminal
+    /// accepts. With this flag the clipboard receives
+    /// 'treeclip+gzip:<base64 of gzip>' instead of the plain text.
+    ///
+    /// NOTE: the receiving side must strip the marker and inflate the
+    /// payload itself; a plain paste shows the encoded text.
+    /// Only applies to --clipboard-backend osc52.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub compress_clipboard: bool,
+
+    /// Copy the statistics to the clipboard instead of the bundle
+    ///
+    /// Places a plain-text summary (characters, lines, words,
+    /// tokens, size; no colors) on the clipboard, for pasting a
+    /// quick size report into a chat. The bundle is still written
+    /// to the output as usual. Honors --clipboard-backend.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with = "clipboard",
+        verbatim_doc_comment
+    )]
+    pub stats_to_clipboard: bool,
+
+    /// Show detailed statistics about the extracted content
+    ///
+    /// Displays:
+    ///   • Total lines, words, and characters
+    ///   • File size in human-readable format
+    ///   • Fun emoji feedback based on size 🐣🐘🐋
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub stats: bool,
+
+    /// Break the statistics down by file extension
+    ///
+    /// Adds a table below the statistics box with the files, lines,
+    /// and bytes of each extension, the busiest extension first.
+    ///
+    /// Example:
+    ///   treeclip run --stats --stats-by-ext
+    ///   → rs: 42 files, 12,000 lines, 410.2 KB
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub stats_by_ext: bool,
+
+    /// Print the first lines of the bundle once it's written
+    ///
+    /// A quick check that headers and format look right without
+    /// opening an editor. Shows 20 lines unless --preview-lines
+    /// says otherwise.
+    ///
+    /// Example:
+    ///   treeclip run --preview --format markdown
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["stdout", "split_by_dir", "split_tokens"],
+        verbatim_doc_comment
+    )]
+    pub preview: bool,
+
+    /// How many lines --preview prints
+    ///
+    /// Example:
+    ///   treeclip run --preview --preview-lines 50
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = validate_positive,
+        requires = "preview",
+        verbatim_doc_comment
+    )]
+    pub preview_lines: Option<usize>,
+
+    /// How words are counted in the statistics
+    ///
+    /// Modes:
+    ///   whitespace  runs of non-whitespace, like `wc -w` (default)
+    ///   alnum       runs of letters and digits, so `a.b,c` is 3
+    ///               words; closer to a prose count for code
+    #[arg(
+        long,
+        value_enum,
+        ignore_case = true,
+        default_value_t = WordMode::Whitespace,
+        value_name = "MODE",
+        verbatim_doc_comment
+    )]
+    pub word_mode: WordMode,
+
+    /// Fail the run if any included file is empty
+    ///
+    /// A zero-byte file usually means a generation step broke.
+    /// Instead of bundling it silently, treeclip stops with an
+    /// error naming the empty file.
+    ///
+    /// Useful as a quality gate in CI/CD pipelines.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub fail_on_empty: bool,
+
+    /// Fail the run if any included file contains a secret
+    ///
+    /// Scans every file before anything is bundled for high-confidence
+    /// secrets: private keys, AWS access key IDs, and GitHub, Slack,
+    /// Google API, and Stripe live tokens. On a match treeclip stops
+    /// with an error naming the file and the kind of secret, so
+    /// nothing reaches the output or the clipboard.
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub abort_on_secret: bool,
+
+    /// Skip files containing a line longer than N characters
+    ///
+    /// Minified JS/CSS and generated blobs pack everything into a few
+    /// enormous lines that waste tokens and aren't human-useful.
+    /// Skipped files are reported with a notice.
+    ///
+    /// Example:
+    ///   --max-line-length 5000
+    #[arg(long, value_name = "N", verbatim_doc_comment)]
+    pub max_line_length: Option<usize>,
+
+    /// Skip files with more than N lines
+    ///
+    /// Log files and data dumps can run to millions of lines. Line
+    /// count catches these better than byte size; the scan stops as
+    /// soon as the cap is passed. Skipped files are reported with a
+    /// notice.
+    ///
+    /// Example:
+    ///   --max-lines 10000
+    #[arg(long, value_name = "N", verbatim_doc_comment)]
+    pub max_lines: Option<usize>,
+
+    /// Stop the bundle after N lines in total
+    ///
+    /// Counts every line written across all inputs: the --title
+    /// header, file headers, content, and the blank lines between
+    /// files. The file that crosses the limit is cut at a line
+    /// boundary and the bundle ends with a notice:
+    ///   ... (truncated by --max-total-lines)
+    ///
+    /// Example:
+    ///   --max-total-lines 5000
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with = "split_by_dir",
+        verbatim_doc_comment
+    )]
+    pub max_total_lines: Option<usize>,
+
+    /// Leave out the content of files larger than SIZE
+    ///
+    /// Oversized files (minified bundles, lockfiles, dumps) keep
+    /// their header, with a note instead of the content:
+    ///   ==> dist/app.js (skipped: 5.2 MB > 2.0 MB limit)
+    /// Accepts bytes or a K/M/G suffix (powers of 1024).
+    ///
+    /// Examples:
+    ///   --max-file-size 500k
+    ///   --max-file-size 2M
+    #[arg(long, value_name = "SIZE", value_parser = parse_size, verbatim_doc_comment)]
+    pub max_file_size: Option<u64>,
+
+    /// Replace tabs with spaces, to the next multiple of N columns
+    ///
+    /// Keeps indentation intact in viewers and chat inputs that render
+    /// tabs at a different width than the project uses.
+    ///
+    /// Example:
+    ///   --expand-tabs 4
+    #[arg(long, value_name = "N", value_parser = validate_positive, verbatim_doc_comment)]
+    pub expand_tabs: Option<usize>,
+
+    /// Take the --expand-tabs width from .editorconfig files
+    ///
+    /// For each file, the nearest .editorconfig settings (tab_width,
+    /// else a numeric indent_size) override the --expand-tabs width.
+    /// Files no .editorconfig section covers keep the --expand-tabs
+    /// width.
+    ///
+    /// Example:
+    ///   --expand-tabs 4 --editorconfig
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "expand_tabs",
+        verbatim_doc_comment
+    )]
+    pub editorconfig: bool,
+
+    /// Remove ANSI escape sequences (colors, cursor moves, titles)
+    ///
+    /// Log files and captured terminal output carry them; they show
+    /// up as garbage like '[31m' once pasted. Covers CSI sequences,
+    /// OSC sequences (ended by BEL or ESC \), and two-byte escapes.
     ///
-    /// Examples:
-    ///   treeclip run .
-    ///   treeclip run ./src
-    ///   treeclip run ~/projects/my-app
-    ///   treeclip run . src some/other/input/path
+    /// Example:
+    ///   treeclip run logs/ --strip-ansi
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub strip_ansi: bool,
+
+    /// Prefix each content line with its line number
+    ///
+    /// Numbers restart at 1 for every file and are right-aligned to
+    /// the width of that file's last line number. With --format
+    /// markdown they go inside the code fence.
+    ///
+    /// Example:
+    ///   --line-numbers  →   9 | fn main() {
+    ///                      10 |     run();
     #[arg(
-        default_value = ".",
-        value_parser = validate_path,
-        value_hint = ValueHint::DirPath,
-        verbatim_doc_comment,
-        num_args = 1..,
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["as_patch", "match_context", "base64"],
+        verbatim_doc_comment
     )]
-    pub input_paths: Vec<PathBuf>,
+    pub line_numbers: bool,
 
-    /// Output file path for the extracted content
+    /// Start each directory's files with a summary header
     ///
-    /// Where to save the bundled output. If not specified,
-    /// creates 'treeclip_temp.txt' in the current directory.
+    /// Written once per directory, with the count and total size of
+    /// the files directly in it. A directory's files are listed
+    /// together, before those of its subdirectories (src/a.rs,
+    /// src/z.rs, then src/m/m.rs). Needs the default --sort path.
     ///
-    /// Examples:
-    ///   -o output.txt
-    ///   --output-path ./exports/bundle.txt
+    /// Example:
+    ///   --dir-headers  →  ## src/ (4 files, 12.0 KB)
     #[arg(
-        short,
         long,
-        default_value = ".",
-        value_parser = validate_path,
-        value_name = "FILE",
-        value_hint = ValueHint::FilePath,
+        default_value_t = false,
+        conflicts_with_all = ["recent", "merge_ext", "order_by_include"],
         verbatim_doc_comment
     )]
-    pub output_path: Option<PathBuf>,
+    pub dir_headers: bool,
 
-    /// Root directory for .treeclipignore file lookup
+    /// Include binary files encoded as text instead of skipping them
     ///
-    /// Specifies where to search for the .treeclipignore file.
-    /// Usually you don't need to change this.
+    /// Files with NUL bytes near the start, or that aren't valid
+    /// UTF-8, are binary. By default they appear only as a header:
+    ///   ==> logo.png (binary, 12.3 KB skipped)
+    /// With this flag their bytes follow the header, base64 (the
+    /// default) or hex encoded.
+    ///
+    /// Examples:
+    ///   --include-binary
+    ///   --include-binary=hex
     #[arg(
         long,
-        default_value = ".",
-        value_parser = validate_path,
-        value_name = "DIR",
-        value_hint = ValueHint::DirPath,
-        hide = true  // Hide from help unless --help is used
+        value_enum,
+        value_name = "ENCODING",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "base64",
+        verbatim_doc_comment
     )]
-    pub root: Option<PathBuf>,
+    pub include_binary: Option<BinaryEncoding>,
 
-    /// Exclude files/folders matching these glob patterns
-    ///
-    /// Can be specified multiple times. Supports glob patterns
-    /// like '*.log', 'node_modules', 'target/**', etc.
+    /// Encode every file's content as base64
     ///
-    /// Common patterns:
-    ///   -e node_modules      (exclude directory)
-    ///   -e '*.log'           (exclude all .log files)
-    ///   -e 'target'          (exclude Rust build dir)
-    ///   -e '__pycache__'     (exclude Python cache)
+    /// Each header becomes '==> path (base64)' followed by the
+    /// file's raw bytes, base64 encoded and wrapped. The bundle
+    /// then survives any transport byte for byte, whatever the
+    /// files contain; 'treeclip unbundle' decodes it back.
     ///
-    /// Tip: Use .treeclipignore file for permanent exclusions!
+    /// Example:
+    ///   treeclip run . --base64 -o bundle.txt
     #[arg(
-        short,
         long,
-        value_name = "PATTERN",
-        action = ArgAction::Append,
+        default_value_t = false,
+        conflicts_with_all = ["as_patch", "merge_ext", "match_context"],
         verbatim_doc_comment
     )]
-    pub exclude: Vec<String>,
+    pub base64: bool,
 
-    /// Copy the output to system clipboard
+    /// Number of threads reading files in parallel
     ///
-    /// After extraction, automatically copies the entire
-    /// output to your clipboard for easy pasting into AI chats.
+    /// Files are read and formatted in parallel, then written in
+    /// order, so the output is identical for any N (files are
+    /// sorted by path). Defaults to the number of CPU cores; 1
+    /// reads sequentially. Open files stay capped by
+    /// --max-open-files.
     ///
-    /// Platform notes:
-    ///   • Windows/macOS: Works out of the box
-    ///   • Linux: Requires xclip or wl-clipboard
-    #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
-    pub clipboard: bool,
+    /// Example:
+    ///   --jobs 4
+    #[arg(short, long, value_name = "N", value_parser = validate_positive, verbatim_doc_comment)]
+    pub jobs: Option<usize>,
 
-    /// Show detailed statistics about the extracted content
+    /// Maximum number of files open for reading at once
     ///
-    /// Displays:
-    ///   • Total lines, words, and characters
-    ///   • File size in human-readable format
-    ///   • Fun emoji feedback based on size 🐣🐘🐋
-    #[arg(long, default_value_t = false, verbatim_doc_comment)]
-    pub stats: bool,
+    /// Guards against "too many open files" errors on systems
+    /// with a low `ulimit -n`. Values below 1 are treated as 1.
+    #[arg(
+        long,
+        value_name = "K",
+        default_value_t = limiter::DEFAULT_MAX_OPEN_FILES,
+        verbatim_doc_comment
+    )]
+    pub max_open_files: usize,
+
+    /// Abort if bundling takes longer than SECS seconds
+    ///
+    /// Guards automation against stalls (e.g. a hung network
+    /// filesystem). On timeout the partial output is removed and
+    /// treeclip exits with an error. Output that can't be taken
+    /// back (stdout, or split bundles) doesn't combine with it.
+    ///
+    /// NOTE: a read stuck in a blocking syscall can't be interrupted;
+    /// it is abandoned and dies with the process (best-effort).
+    #[arg(
+        long,
+        value_name = "SECS",
+        conflicts_with_all = ["stdout", "split_tokens", "split_by_dir"],
+        verbatim_doc_comment
+    )]
+    pub timeout: Option<u64>,
 
     /// Open the output file in your default text editor
     ///
@@ -109,6 +1316,43 @@ pub struct RunArgs {
     #[arg(long, default_value_t = false, verbatim_doc_comment)]
     pub editor: bool,
 
+    /// CLI editors to try if the default editor fails
+    ///
+    /// Comma-separated and tried in order; each is looked up on
+    /// PATH and the first that launches is used. '$NAME' entries
+    /// are read from the environment (skipped when unset).
+    ///
+    /// Default: $VISUAL,$EDITOR,nano,vi
+    ///
+    /// Example:
+    ///   --fallback-editors 'micro,$EDITOR,vim'
+    #[arg(
+        long,
+        value_name = "EDITORS",
+        value_delimiter = ',',
+        requires = "editor",
+        verbatim_doc_comment
+    )]
+    pub fallback_editors: Vec<String>,
+
+    /// Open the output with this command instead of the default editor
+    ///
+    /// The command is split on whitespace into a program (looked up
+    /// on PATH) and its arguments; the output path is appended.
+    /// treeclip waits for the command to exit before --delete, so
+    /// GUI editors need their own wait flag to stay open.
+    ///
+    /// Example:
+    ///   treeclip run --editor --editor-cmd 'code --wait' --delete
+    #[arg(
+        long,
+        value_name = "CMD",
+        requires = "editor",
+        conflicts_with = "fallback_editors",
+        verbatim_doc_comment
+    )]
+    pub editor_cmd: Option<String>,
+
     /// Delete the output file after closing the editor
     ///
     /// Only works when used with --editor flag.
@@ -124,6 +1368,29 @@ pub struct RunArgs {
     )]
     pub delete: bool,
 
+    /// How to display per-file progress while bundling
+    ///
+    /// Styles:
+    ///   dots     a dot for every few files
+    ///   bar      a bar filling up towards the total file count
+    ///   spinner  a spinner with a running file count
+    ///   emoji    the cute tree emoji counter 🌱🌳
+    ///   none     no progress output
+    ///
+    /// Defaults to emoji when stderr is a terminal (bar with
+    /// --verbose), and none otherwise (always none in --fast-mode
+    /// and --quiet unless chosen explicitly).
+    /// Progress is drawn on stderr, so it never ends up in
+    /// redirected output.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "STYLE",
+        ignore_case = true,
+        verbatim_doc_comment
+    )]
+    pub progress_style: Option<ProgressStyle>,
+
     /// Enable verbose output with detailed progress information
     ///
     /// Shows:
@@ -135,6 +1402,23 @@ pub struct RunArgs {
     #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
     pub verbose: bool,
 
+    /// Print nothing but errors
+    ///
+    /// Silences the banner, configuration log, progress, ignore
+    /// file notices, statistics, and warnings. Implies --fast-mode.
+    /// A bundle written to stdout is still written.
+    ///
+    /// Example:
+    ///   treeclip run ./src -o bundle.txt -q
+    #[arg(
+        short,
+        long,
+        default_value_t = false,
+        conflicts_with = "verbose",
+        verbatim_doc_comment
+    )]
+    pub quiet: bool,
+
     /// Skip hidden files and folders (starting with '.')
     ///
     /// Enabled by default. Use --no-skip-hidden to include
@@ -174,6 +1458,115 @@ pub struct RunArgs {
     ///   • When you're in a hurry!
     #[arg(short, long, default_value_t = false, verbatim_doc_comment)]
     pub fast_mode: bool,
+
+    /// Print plain text without colors
+    ///
+    /// Colors are also left out when the NO_COLOR environment
+    /// variable is set (to anything but an empty string), or
+    /// when the terminal output is redirected to a file or pipe.
+    ///
+    /// Example:
+    ///   treeclip run --no-color 2> treeclip.log
+    #[arg(long, default_value_t = false, verbatim_doc_comment)]
+    pub no_color: bool,
+}
+
+/// Arguments for the `stats` command.
+#[derive(clap::Args)]
+pub struct StatsArgs {
+    /// Bundle file or directories to analyze
+    ///
+    /// A single file is read as an existing bundle (any file
+    /// produced by `treeclip run`, or any text file, really).
+    /// Directories are traversed as `treeclip run` would, reporting
+    /// the bundle it would produce without writing anything.
+    ///
+    /// Examples:
+    ///   treeclip stats treeclip_temp.txt
+    ///   treeclip stats ./src ./tests -e target
+    #[arg(
+        default_value = ".",
+        value_parser = validate_path,
+        value_name = "PATHS",
+        value_hint = ValueHint::AnyPath,
+        num_args = 1..,
+        verbatim_doc_comment
+    )]
+    pub paths: Vec<PathBuf>,
+
+    /// Exclude files/folders matching these glob patterns
+    ///
+    /// Same as `run --exclude`; only used when traversing.
+    ///
+    /// Example:
+    ///   -e node_modules -e '*.log'
+    #[arg(
+        short,
+        long,
+        value_name = "PATTERN",
+        action = ArgAction::Append,
+        verbatim_doc_comment
+    )]
+    pub exclude: Vec<String>,
+
+    /// Skip hidden files and folders (starting with '.')
+    ///
+    /// Same as `run --skip-hidden`; only used when traversing.
+    #[arg(short = 'H', long, default_value_t = true, verbatim_doc_comment)]
+    pub skip_hidden: bool,
+
+    /// How words are counted in the statistics
+    ///
+    /// Modes:
+    ///   whitespace  runs of non-whitespace, like `wc -w` (default)
+    ///   alnum       runs of letters and digits, so `a.b,c` is 3
+    ///               words; closer to a prose count for code
+    #[arg(
+        long,
+        value_enum,
+        ignore_case = true,
+        default_value_t = WordMode::Whitespace,
+        value_name = "MODE",
+        verbatim_doc_comment
+    )]
+    pub word_mode: WordMode,
+}
+
+/// Arguments for the `unbundle` command.
+#[derive(clap::Args)]
+pub struct UnbundleArgs {
+    /// Bundle file to extract
+    ///
+    /// A text bundle from `treeclip run`; '(base64)' sections (from
+    /// --base64) and encoded binary files are decoded byte for byte.
+    ///
+    /// Example:
+    ///   treeclip unbundle bundle.txt
+    #[arg(
+        value_parser = validate_path,
+        value_name = "BUNDLE",
+        value_hint = ValueHint::FilePath,
+        verbatim_doc_comment
+    )]
+    pub bundle: PathBuf,
+
+    /// Directory the files are recreated in
+    ///
+    /// Each file lands at its header path below this directory;
+    /// missing parent directories are created and existing files
+    /// are overwritten.
+    ///
+    /// Example:
+    ///   treeclip unbundle bundle.txt -o ./restored
+    #[arg(
+        short,
+        long,
+        default_value = ".",
+        value_name = "DIR",
+        value_hint = ValueHint::DirPath,
+        verbatim_doc_comment
+    )]
+    pub output_dir: PathBuf,
 }
 
 // -------------------------------------------- Private Helper Functions --------------------------------------------
@@ -188,7 +1581,113 @@ fn validate_path(s: &str) -> Result<PathBuf, String> {
     Ok(PathBuf::from(s))
 }
 
+/// Validates that a count is a whole number greater than zero.
+pub(crate) fn validate_positive(s: &str) -> Result<usize, String> {
+    match s.trim().parse::<usize>() {
+        Ok(0) => Err("Value must be greater than zero".to_string()),
+        Ok(value) => Ok(value),
+        Err(e) => Err(format!("Invalid number '{s}': {e}")),
+    }
+}
+
+/// Parses a byte size: a plain number of bytes, or one with a K, M, or G suffix
+/// (powers of 1024, an optional trailing 'B', any case), e.g. `1024`, `10k`, `1.5MB`.
+pub(crate) fn parse_size(s: &str) -> Result<u64, String> {
+    let trimmed = s.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    let number = lower.strip_suffix('b').unwrap_or(&lower);
+    let (number, multiplier) = match number.char_indices().last() {
+        Some((i, 'k')) => (&number[..i], 1u64 << 10),
+        Some((i, 'm')) => (&number[..i], 1 << 20),
+        Some((i, 'g')) => (&number[..i], 1 << 30),
+        _ => (number, 1),
+    };
+
+    let invalid = || format!("Invalid size '{s}': expected bytes or a K/M/G suffix, e.g. 500k");
+    if let Ok(bytes) = number.trim().parse::<u64>() {
+        return bytes.checked_mul(multiplier).ok_or_else(invalid);
+    }
+    match number.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 && multiplier > 1 => {
+            Ok((value * multiplier as f64).round() as u64)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses a comma-separated extension list into lowercase extensions without their dot,
+/// e.g. `PNG,.jpg` → `{"png", "jpg"}`.
+fn parse_extensions(s: &str) -> Result<HashSet<String>, String> {
+    s.split(',')
+        .map(|extension| extension.trim().trim_start_matches('.'))
+        .filter(|extension| !extension.is_empty())
+        .map(|extension| {
+            if extension.contains(['/', '\\']) {
+                Err(format!(
+                    "Invalid extension '{extension}': expected e.g. png or tar.gz"
+                ))
+            } else {
+                Ok(extension.to_lowercase())
+            }
+        })
+        .collect()
+}
+
+/// Parses a UTC date with an optional time, leniently: `-`, `/`, or `.` separators (or
+/// none, `20240301`), single-digit months and days, and `T` or a space before `HH:MM[:SS]`.
+fn parse_date(s: &str) -> Result<SystemTime, String> {
+    let invalid = || format!("Invalid date '{s}': expected e.g. 2024-03-01 or 2024-03-01T12:30");
+    let trimmed = s.trim().trim_end_matches(['Z', 'z']);
+    let (date, time) = match trimmed.split_once(['T', 't', ' ']) {
+        Some((date, time)) => (date, Some(time.trim())),
+        None => (trimmed, None),
+    };
+
+    let parts: Vec<&str> = if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) {
+        vec![&date[..4], &date[4..6], &date[6..]]
+    } else {
+        date.split(['-', '/', '.']).collect()
+    };
+    let [year, month, day] = parts.as_slice() else {
+        return Err(invalid());
+    };
+    let (Ok(year), Ok(month), Ok(day)) = (year.parse(), month.parse(), day.parse()) else {
+        return Err(invalid());
+    };
+
+    // Fractional seconds are dropped
+    let seconds_of_day = match time {
+        None => 0,
+        Some(time) => {
+            let fields: Vec<&str> = time.split('.').next().unwrap_or(time).split(':').collect();
+            let numbers: Option<Vec<u64>> = fields.iter().map(|field| field.parse().ok()).collect();
+            match numbers.as_deref() {
+                Some(&[hours, minutes]) if hours < 24 && minutes < 60 => {
+                    hours * 3600 + minutes * 60
+                }
+                Some(&[hours, minutes, seconds]) if hours < 24 && minutes < 60 && seconds < 60 => {
+                    hours * 3600 + minutes * 60 + seconds
+                }
+                _ => return Err(invalid()),
+            }
+        }
+    };
+    utils::utc_instant(year, month, day, seconds_of_day).ok_or_else(invalid)
+}
+
+/// Checks that a `--header-format` template is a single line containing `{path}`.
+fn parse_header_format(s: &str) -> Result<String, String> {
+    if !s.contains("{path}") {
+        return Err(format!("Header template '{s}' must contain {{path}}"));
+    }
+    if s.contains(['\n', '\r']) {
+        return Err("Header template must be a single line".to_string());
+    }
+    Ok(s.to_string())
+}
+
 #[cfg(test)]
+#[allow(clippy::needless_borrows_for_generic_args)] // Arg lists are passed as `&[..]` slices
 mod args_tests {
     use super::*;
     use crate::cli::{Cli, Commands};
@@ -214,6 +1713,77 @@ mod args_tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_extensions_normalizes() {
+        let parsed = parse_extensions("PNG, .jpg,,tar.gz").unwrap();
+        assert_eq!(
+            parsed,
+            HashSet::from(["png".to_string(), "jpg".into(), "tar.gz".into()])
+        );
+        assert!(parse_extensions("").unwrap().is_empty());
+        assert!(parse_extensions("img/png").is_err());
+    }
+
+    #[test]
+    fn test_parse_date_is_lenient() {
+        let expected = utils::utc_instant(2024, 3, 1, 0).unwrap();
+        for date in [
+            "2024-03-01",
+            "2024/3/1",
+            "2024.03.01",
+            "20240301",
+            " 2024-03-01Z ",
+        ] {
+            assert_eq!(parse_date(date), Ok(expected), "rejected {date:?}");
+        }
+        assert_eq!(
+            parse_date("2024-03-01T12:30"),
+            Ok(utils::utc_instant(2024, 3, 1, 12 * 3600 + 30 * 60).unwrap())
+        );
+        assert_eq!(
+            parse_date("2024-03-01 00:00:05.25"),
+            Ok(utils::utc_instant(2024, 3, 1, 5).unwrap())
+        );
+
+        for invalid in [
+            "",
+            "2024",
+            "2024-02-30",
+            "2024-03-01T25:00",
+            "03/01/24x",
+            "yesterday",
+        ] {
+            assert!(parse_date(invalid).is_err(), "accepted {invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_size_suffixes() {
+        assert_eq!(parse_size("1024"), Ok(1024));
+        assert_eq!(parse_size("10k"), Ok(10 * 1024));
+        assert_eq!(parse_size("3M"), Ok(3 * 1024 * 1024));
+        assert_eq!(parse_size("1.5MB"), Ok(1536 * 1024));
+        assert_eq!(parse_size("2g"), Ok(2 * 1024 * 1024 * 1024));
+
+        for invalid in ["", "abc", "10x", "-5k", "1.5", "M", "99999999999999999999"] {
+            assert!(parse_size(invalid).is_err(), "accepted {invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_header_format() {
+        assert_eq!(
+            parse_header_format("// File: {path}"),
+            Ok("// File: {path}".to_string())
+        );
+        assert!(
+            parse_header_format("// File:")
+                .unwrap_err()
+                .contains("{path}")
+        );
+        assert!(parse_header_format("{path}\n---").is_err());
+    }
+
     #[test]
     fn test_run_args_default_values() {
         let cli = Cli::parse_from(&["treeclip", "run"]);
@@ -229,7 +1799,9 @@ mod args_tests {
                 assert!(!args.fast_mode);
                 assert!(args.skip_hidden);
                 assert!(args.exclude.is_empty());
+                assert_eq!(args.format, OutputFormat::Text);
             }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -243,6 +1815,41 @@ mod args_tests {
                 assert_eq!(args.input_paths[1], PathBuf::from("src"));
                 assert_eq!(args.input_paths[2], PathBuf::from("some/other/input/path"));
             }
+            _ => panic!("expected the run command"),
+        }
+    }
+
+    #[test]
+    fn test_stdout_flag_conflicts_with_output_path() {
+        let cli = Cli::parse_from(["treeclip", "run", ".", "--stdout"]);
+        match cli.command {
+            Commands::Run(args) => assert!(args.stdout),
+            _ => panic!("expected the run command"),
+        }
+
+        let result = Cli::try_parse_from(["treeclip", "run", ".", "--stdout", "-o", "out.txt"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_print_output_path_conflicts_with_split_output() {
+        for split in [&["--split-tokens", "1000"][..], &["--split-by-dir"]] {
+            let args = ["treeclip", "run", ".", "--print-output-path"];
+            let result = Cli::try_parse_from(args.iter().chain(split));
+            assert!(result.is_err(), "accepted {split:?}");
+        }
+    }
+
+    #[test]
+    fn test_timeout_conflicts_with_unremovable_output() {
+        for output in [
+            &["--stdout"][..],
+            &["--split-tokens", "1000"],
+            &["--split-by-dir"],
+        ] {
+            let args = ["treeclip", "run", ".", "--timeout", "5"];
+            let result = Cli::try_parse_from(args.iter().chain(output));
+            assert!(result.is_err(), "accepted {output:?}");
         }
     }
 
@@ -253,6 +1860,7 @@ mod args_tests {
             Commands::Run(args) => {
                 assert!(args.fast_mode);
             }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -276,6 +1884,18 @@ mod args_tests {
                 assert!(args.exclude.contains(&"target".to_string()));
                 assert!(args.exclude.contains(&"*.log".to_string()));
             }
+            _ => panic!("expected the run command"),
+        }
+    }
+
+    #[test]
+    fn test_format_json_string() {
+        let cli = Cli::parse_from(["treeclip", "run", ".", "--format", "json-string"]);
+        match cli.command {
+            Commands::Run(args) => {
+                assert_eq!(args.format, OutputFormat::JsonString);
+            }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -294,6 +1914,7 @@ mod args_tests {
                 assert!(args.editor);
                 assert!(args.delete);
             }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -306,6 +1927,7 @@ mod args_tests {
                 assert!(args.verbose);
                 assert!(args.fast_mode);
             }
+            _ => panic!("expected the run command"),
         }
     }
 
@@ -317,6 +1939,7 @@ mod args_tests {
                 assert!(args.clipboard);
                 assert!(args.stats);
             }
+            _ => panic!("expected the run command"),
         }
     }
 }
@@ -0,0 +1,83 @@
+//! json_schema - Execution logic for the json-schema command, describing `--format json` output.
+
+use crate::core::traversal::walker::JsonFile;
+use anyhow::Context;
+use std::io::{self, Write};
+
+/// Executes the json-schema command, printing the JSON Schema of a `--format json` bundle.
+///
+/// # Errors
+///
+/// Returns an error if the schema cannot be serialized or written to stdout.
+pub fn execute() -> anyhow::Result<()> {
+    let schema = serde_json::to_string_pretty(&bundle_schema())?;
+    write_schema(&mut io::stdout().lock(), &schema)
+}
+
+/// Writes `schema` to `out`; a reader that stops early (e.g. `| head`) isn't an error.
+fn write_schema(out: &mut impl Write, schema: &str) -> anyhow::Result<()> {
+    match writeln!(out, "{schema}").and_then(|()| out.flush()) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.with_context(|| "Failed to write the schema to stdout"),
+    }
+}
+
+/// Builds the schema of a `--format json` bundle: an array of [`JsonFile`] elements.
+///
+/// Derived from the struct the bundle is serialized from, so the two can't drift apart.
+fn bundle_schema() -> schemars::Schema {
+    schemars::schema_for!(Vec<JsonFile>)
+}
+
+#[cfg(test)]
+mod json_schema_tests {
+    use super::*;
+
+    /// A stdout whose reader has gone away.
+    struct ClosedPipe;
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_closed_pipe_is_a_clean_exit() -> anyhow::Result<()> {
+        write_schema(&mut ClosedPipe, "{}")?;
+
+        let mut out = Vec::new();
+        write_schema(&mut out, "{}")?;
+        assert_eq!(out, b"{}\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_lists_file_properties() -> anyhow::Result<()> {
+        let schema: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&bundle_schema())?)?;
+        assert_eq!(schema["type"], "array");
+
+        let item = &schema["items"]["$ref"];
+        let name = item.as_str().and_then(|r| r.rsplit('/').next()).unwrap();
+        let file = &schema["$defs"][name];
+        assert!(file["properties"]["path"].is_object());
+        assert!(file["properties"]["content"].is_object());
+        assert!(
+            file["required"]
+                .as_array()
+                .unwrap()
+                .contains(&"path".into())
+        );
+        // Flags are only ever left out, never null
+        for flag in ["binary", "skipped", "stub"] {
+            assert_eq!(file["properties"][flag]["type"], "boolean");
+            assert!(!file["required"].as_array().unwrap().contains(&flag.into()));
+        }
+        Ok(())
+    }
+}
@@ -1,2 +1,5 @@
 pub mod args;
+pub mod json_schema;
 pub mod run;
+pub mod stats;
+pub mod unbundle;
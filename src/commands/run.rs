@@ -1,60 +1,223 @@
 //! run - Main execution logic for the run command, orchestrating all operations.
 
-use super::args::RunArgs;
-use crate::core::ui::{animations, banner, formatter, messages};
-use crate::core::{clipboard, editor, traversal::walker};
+use super::args::{EventFormat, OutputFormat, RunArgs, SortOrder, WordMode};
+use crate::core::errors::{FileSystemError, TraversalError};
+use crate::core::traversal::{events::EventSink, sink::BundleSink, walker};
+use crate::core::ui::{animations, banner, console, formatter, highlight, messages};
+use crate::core::{clipboard, editor, exclude, utils};
+use anyhow::Context;
+use clap::ValueEnum;
+use colored::Colorize;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::ffi::OsStr;
+use std::io::{self, BufRead, IsTerminal, Read, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 use std::{env, fs};
+use tempfile::NamedTempFile;
+
+/// Output path that means "write the bundle to stdout".
+const STDOUT_PATH: &str = "-";
+
+/// Bytes of the SHA-256 digest used in `--hash-suffix` file names (two hex digits each).
+const HASH_SUFFIX_BYTES: usize = 4;
+
+/// Lines `--preview` prints unless `--preview-lines` is given.
+const DEFAULT_PREVIEW_LINES: usize = 20;
 
 /// Executes the main treeclip run command with the provided arguments.
 ///
 /// This orchestrates the entire flow: configuration, traversal, clipboard, stats, and editor.
-pub fn execute(mut args: RunArgs) -> anyhow::Result<()> {
+pub fn execute(args: RunArgs, rng: &mut impl Rng) -> anyhow::Result<()> {
+    execute_with_sink(args, BundleSink::new(io::stdout()), rng)
+}
+
+/// Runs the command, streaming the bundle into `bundle_sink` when `--stdout` is set.
+pub(crate) fn execute_with_sink(
+    mut args: RunArgs,
+    mut bundle_sink: BundleSink,
+    rng: &mut impl Rng,
+) -> anyhow::Result<()> {
+    // Keep stdout clean for the bundle (or output path, or preview): all decorative output
+    // goes to stderr
+    console::route_to_stderr(
+        writes_to_stdout(&args) || args.print_output_path || args.dry_run_json || args.dry_run,
+    );
+
+    // Colors only help someone reading a terminal; this covers banners, boxes, and animations
+    let decorations_on_terminal = if console::is_routed_to_stderr() {
+        io::stderr().is_terminal()
+    } else {
+        io::stdout().is_terminal()
+    };
+    if !color_enabled(
+        &args,
+        env::var_os("NO_COLOR").as_deref(),
+        decorations_on_terminal,
+    ) {
+        colored::control::set_override(false);
+    }
+
+    // Quiet mode silences everything but errors (and the bundle itself, on stdout)
+    if args.quiet {
+        console::set_quiet(true);
+    }
+
+    // Banners and animations have no place in a pipeline, so stdout implies fast mode
+    if writes_to_stdout(&args) || args.dry_run_json || args.dry_run || args.quiet {
+        args.fast_mode = true;
+    }
+
+    // --canonical pins the options that would otherwise vary between runs
+    if args.canonical {
+        args.stable_timestamps = true;
+    }
+
     // Display welcome banner (respects fast mode)
     if !args.fast_mode {
-        banner::print_welcome();
+        banner::print_welcome(rng);
+    }
+
+    // Replace the input paths with the file list on the clipboard, if requested
+    if args.input_from_clipboard {
+        let mut source = clipboard::system_source()?;
+        apply_clipboard_inputs(&mut args, &mut source)?;
+    }
+
+    // Add the paths listed in a file or on stdin, if requested
+    if let Some(list) = args.paths_from.clone() {
+        let text = read_path_list(&list)?;
+        apply_listed_inputs(&mut args, &text)?;
     }
 
     // Normalize paths to absolute paths
     normalize_paths(&mut args)?;
 
-    let root = args.root.as_ref().unwrap();
-    let inputs = &args.input_paths;
-    let output = args.output_path.as_ref().unwrap();
+    // Nested inputs would bundle the same files twice
+    check_overlapping_inputs(&mut args);
+
+    // Spell out the plan before doing anything, if requested
+    if args.explain {
+        explain_plan(&args)?;
+    }
+
+    // Preview the selection and stop before anything is written
+    if args.dry_run_json || (args.dry_run && args.format == OutputFormat::Json) {
+        return write_dry_run_json(&args, &mut bundle_sink);
+    }
+    if args.dry_run {
+        return write_dry_run(&args, &mut bundle_sink);
+    }
+
+    // With --stdout, the walkers write straight into the sink; only if the finished bundle
+    // is needed as a whole is it assembled in the scratch directory and streamed out at the end
+    let _stdout_staging = if args.stdout {
+        Some(stage_stdout_output(&mut args)?)
+    } else {
+        None
+    };
+    let direct_sink = streams_directly(&args).then(|| bundle_sink.clone());
 
     // Log configuration
     log_config(&args)?;
 
-    // Execute traversal for each input path
-    let mut any_success = false;
-    for input in inputs {
-        match execute_traversal(&args, root, input, output) {
-            Ok(()) => any_success = true,
-            Err(e) => {
-                // If it's a "No files found" error, continue to next path
-                if e.to_string().contains("No files found") {
-                    eprintln!("Warning: No files found in directory: {}", input.display());
-                    continue;
-                } else {
-                    return Err(e);
-                }
-            }
+    // With --hash-suffix, the bundle is written to a temp file and renamed once its hash is known
+    let hash_staging = if args.hash_suffix {
+        Some(stage_hashed_output(&mut args)?)
+    } else {
+        None
+    };
+
+    let output = &args.output_path.clone().unwrap();
+
+    // A symlinked output is written through, unless --no-follow-output-symlink forbids it
+    if args.no_follow_output_symlink {
+        refuse_symlinked_output(output)?;
+    }
+
+    // Start from a fresh output file (with the title header, if any)
+    let mut content_stats = prepare_output(&args, output, direct_sink.as_ref())?;
+
+    // The header is part of the bundle, so --max-total-lines counts it too
+    if let Some(lines_left) = args.max_total_lines.as_mut() {
+        *lines_left = lines_left.saturating_sub(content_stats.newlines());
+    }
+
+    // Open the --events stream, if requested
+    let events = open_events(&args)?;
+
+    // Execute traversal for each input path (on a worker thread when a timeout is set)
+    let report = match args.timeout {
+        Some(seconds) => {
+            let worker_args = args.clone();
+            let worker_events = events.clone();
+            let worker_sink = direct_sink.clone();
+            run_traversal_with_timeout(Duration::from_secs(seconds), output, move || {
+                traverse_inputs(&worker_args, worker_events.as_ref(), worker_sink.as_ref())
+            })?
         }
+        None => traverse_inputs(&args, events.as_ref(), direct_sink.as_ref())?,
+    };
+    if let Some(events) = &events {
+        events.done(report.file_sizes.len());
     }
+    content_stats.merge(report.content);
 
-    // If no directories had any files, return an error
-    if !any_success {
-        return Err(anyhow::anyhow!(
-            "No files found in any of the specified directories"
-        ));
+    // Report which exclusion patterns are pulling their weight
+    if args.pattern_stats {
+        show_pattern_stats(&report.pattern_stats);
+    }
+
+    // Show what the bundle is made of
+    if args.size_histogram {
+        show_size_histogram(&report.file_sizes);
+    }
+    if args.language_report {
+        show_language_report(&report.file_paths);
+    }
+
+    // Apply whole-bundle transformations (e.g. output format wrapping)
+    finalize_output(&args, output)?;
+
+    // The content is final: move the bundle to its hash-suffixed name
+    let output = match hash_staging {
+        Some((staging, base)) => persist_with_hash(staging, &base)?,
+        None => output.clone(),
+    };
+    let output = output.as_path();
+
+    // Write the path → byte range sidecar for random access into the bundle
+    if let Some(index_path) = &args.index_out {
+        write_file_index(index_path, &report.file_spans)?;
+    }
+
+    // Stream the finished bundle to stdout if requested (and it didn't go there directly)
+    if args.stdout && direct_sink.is_none() {
+        let highlight = should_highlight(&args, io::stdout().is_terminal());
+        emit_bundle(output, &mut bundle_sink, highlight)?;
     }
 
-    // Handle clipboard operations
-    handle_clipboard(&args, output)?;
+    // Handle clipboard operations (the bundle, or only its statistics)
+    if args.stats_to_clipboard {
+        let stats = bundle_stats(&args, output, content_stats)?;
+        copy_stats_to_clipboard(&args, &stats)?;
+    } else {
+        handle_clipboard(&args, output)?;
+    }
 
     // Show statistics if requested
     if args.stats {
-        show_stats_section(&args, output)?;
+        show_stats_section(&args, output, content_stats)?;
+    }
+    if args.stats_by_ext {
+        console::line(report.languages.render());
+    }
+
+    // Let the user eyeball the start of the bundle
+    if args.preview {
+        show_preview(output, args.preview_lines.unwrap_or(DEFAULT_PREVIEW_LINES))?;
     }
 
     // Handle editor operations
@@ -62,12 +225,55 @@ pub fn execute(mut args: RunArgs) -> anyhow::Result<()> {
 
     // Display goodbye message (respects fast mode)
     if !args.fast_mode {
-        banner::print_goodbye();
+        banner::print_goodbye(rng);
+    }
+
+    // Report where the bundle landed, as the only line on stdout
+    if args.print_output_path {
+        let absolute = std::path::absolute(output).unwrap_or_else(|_| output.to_path_buf());
+        writeln!(bundle_sink, "{}", absolute.display())
+            .with_context(|| "Failed to write the output path to stdout")?;
     }
 
     Ok(())
 }
 
+/// Traverses the inputs exactly like a run would, discarding the bundle; returns its statistics.
+///
+/// Nothing is written and the clipboard is left alone: the walkers stream into a null sink.
+/// Like a run, an input without files is skipped with a warning.
+pub(super) fn tally_inputs(mut args: RunArgs) -> anyhow::Result<formatter::StatsTally> {
+    normalize_paths(&mut args)?;
+    check_overlapping_inputs(&mut args);
+
+    let root = args.root.as_ref().unwrap();
+    let output = args.output_path.as_ref().unwrap();
+    let sink = BundleSink::new(io::sink());
+
+    let mut tally = formatter::StatsTally::default();
+    for input in &args.input_paths {
+        let result = walker::Walker::new(root, input, output, &args.exclude)
+            .with_sink(sink.clone())
+            .process_dir(&args);
+        match result {
+            Ok(report) => tally.merge(report.content),
+            Err(e)
+                if matches!(
+                    e.downcast_ref::<TraversalError>(),
+                    Some(TraversalError::NoFilesFound(_))
+                ) =>
+            {
+                console::warning(format!(
+                    "Warning: No files found in directory: {}",
+                    input.display()
+                ));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(tally)
+}
+
 // -------------------------------------------- Private Helper Functions --------------------------------------------
 
 /// Normalizes all path arguments to absolute paths.
@@ -84,11 +290,63 @@ fn normalize_paths(args: &mut RunArgs) -> anyhow::Result<()> {
     }
     args.input_paths = normalized_input_paths;
 
-    // Normalize output path
+    // Offsets index the text bundle; they'd be meaningless once it's wrapped in JSON
+    if args.index_out.is_some() && args.format == OutputFormat::JsonString {
+        anyhow::bail!("--index-out can't index a bundle wrapped as a JSON string");
+    }
+
+    // An empty date window would silently bundle nothing
+    if let (Some(before), Some(after)) = (args.modified_before, args.modified_after)
+        && after >= before
+    {
+        anyhow::bail!("--modified-after must be earlier than --modified-before");
+    }
+
+    // Other orders would scatter a directory's files under several headers
+    if args.dir_headers && args.sort != SortOrder::Path {
+        anyhow::bail!("--dir-headers needs --sort path to keep each directory's files together");
+    }
+
+    // A JSON bundle is one closed value; anything appended would break it
+    if args.append && matches!(args.format, OutputFormat::Json | OutputFormat::JsonString) {
+        anyhow::bail!("--append only extends text and markdown bundles");
+    }
+
+    // A JSON array only holds file elements, all in one output
+    if args.format == OutputFormat::Json
+        && let Some(flag) = json_format_conflict(args)
+    {
+        anyhow::bail!("--format json can't be combined with {flag}");
+    }
+
+    // `-o -` means stdout, following the Unix convention
+    if args.output_path.as_deref() == Some(Path::new(STDOUT_PATH)) {
+        if args.print_output_path {
+            anyhow::bail!("--print-output-path needs an output file, not stdout");
+        }
+        if args.append {
+            anyhow::bail!("--append needs an output file, not stdout");
+        }
+        if args.split_by_dir || args.split_tokens.is_some() {
+            anyhow::bail!("Split output writes several bundles and can't go to stdout");
+        }
+        if args.timeout.is_some() {
+            anyhow::bail!("--timeout can't take back a bundle already streamed to stdout");
+        }
+        args.stdout = true;
+        args.output_path = None;
+    }
+
+    // Normalize output path (a directory of bundles with --split-by-dir)
+    let default_output = if args.split_by_dir {
+        "./treeclip_split"
+    } else {
+        "./treeclip_temp.txt"
+    };
     args.output_path = match &args.output_path {
-        Some(path) if path == Path::new(".") => Some(PathBuf::from("./treeclip_temp.txt")),
+        Some(path) if path == Path::new(".") => Some(PathBuf::from(default_output)),
         Some(path) => Some(path.clone()),
-        None => Some(PathBuf::from("./treeclip_temp.txt")),
+        None => Some(PathBuf::from(default_output)),
     };
 
     // Normalize root path
@@ -101,167 +359,1080 @@ fn normalize_paths(args: &mut RunArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Warns about inputs contained in another input and, with `--dedup-inputs`, drops them.
+///
+/// Inputs are compared by canonical path; ones that can't be resolved are left for
+/// the traversal to report.
+fn check_overlapping_inputs(args: &mut RunArgs) {
+    let canonical: Vec<Option<PathBuf>> = args
+        .input_paths
+        .iter()
+        .map(|input| input.canonicalize().ok())
+        .collect();
+
+    let mut contained = vec![false; canonical.len()];
+    for (index, inner) in canonical.iter().enumerate() {
+        let Some(inner) = inner else { continue };
+        let outer = canonical.iter().enumerate().find(|&(other, outer)| {
+            other != index
+                && outer.as_ref().is_some_and(|outer| {
+                    // Of two identical inputs, only the later one is the duplicate
+                    inner.starts_with(outer) && (inner != outer || other < index)
+                })
+        });
+
+        if let Some((outer, _)) = outer {
+            console::line(messages::Messages::overlapping_input(
+                &args.input_paths[index].display().to_string(),
+                &args.input_paths[outer].display().to_string(),
+                args.dedup_inputs,
+            ));
+            contained[index] = true;
+        }
+    }
+
+    if args.dedup_inputs {
+        let mut contained = contained.into_iter();
+        args.input_paths
+            .retain(|_| !contained.next().unwrap_or(false));
+    }
+}
+
+/// Traverses every input path into the output file, merging the traversal reports.
+fn traverse_inputs(
+    args: &RunArgs,
+    events: Option<&Arc<EventSink>>,
+    sink: Option<&BundleSink>,
+) -> anyhow::Result<walker::TraversalReport> {
+    let root = args.root.as_ref().unwrap();
+    let output = args.output_path.as_ref().unwrap();
+
+    let mut any_success = false;
+    let mut report = walker::TraversalReport::default();
+    // --max-total-lines caps the whole bundle, so each input gets what the previous ones left
+    let mut input_args = args.clone();
+    // The --tree overview of all inputs is already in place, unless each split bundle has its own
+    input_args.tree = args.tree && args.split_by_dir;
+    for input in &args.input_paths {
+        match execute_traversal(&input_args, root, input, output, events, sink) {
+            Ok(input_report) => {
+                if let Some(lines_left) = input_args.max_total_lines.as_mut() {
+                    *lines_left = lines_left.saturating_sub(input_report.content.newlines());
+                }
+                // --max-files is shared the same way; a used-up limit ends the run
+                if let Some(files_left) = input_args.max_files.as_mut() {
+                    *files_left = files_left.saturating_sub(input_report.file_sizes.len());
+                }
+                let truncated = input_report.truncated || input_args.max_files == Some(0);
+                report.merge(input_report);
+                any_success = true;
+                if truncated {
+                    break;
+                }
+            }
+            Err(e) => {
+                // If it's a "No files found" error, continue to next path
+                if matches!(
+                    e.downcast_ref::<TraversalError>(),
+                    Some(TraversalError::NoFilesFound(_))
+                ) {
+                    console::warning(format!(
+                        "Warning: No files found in directory: {}",
+                        input.display()
+                    ));
+                    continue;
+                } else {
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    // If no directories had any files, return an error (unless an empty bundle is fine, or
+    // --append leaves an existing bundle in place)
+    let keeps_existing = args.append && fs::metadata(output).is_ok_and(|meta| meta.len() > 0);
+    if !any_success && args.allow_empty {
+        console::line(messages::Messages::empty_bundle(
+            &output.display().to_string(),
+        ));
+    } else if !any_success && !keeps_existing {
+        return Err(anyhow::anyhow!(
+            "No files found in any of the specified directories"
+        ));
+    }
+
+    // Files were found, but the filters left nothing of them (stubbed, skipped, or empty)
+    if !report.file_paths.is_empty() && report.content_bytes == 0 {
+        console::warning(messages::Messages::empty_content(report.file_paths.len()));
+    }
+
+    Ok(report)
+}
+
+/// Runs `traversal` on a worker thread, giving up after `timeout`.
+///
+/// On timeout the partial output file is removed; `--timeout` conflicts with the outputs
+/// that can't be removed this way (stdout and split bundles). The worker itself can't be
+/// interrupted (it may be stuck in a blocking syscall), so it is abandoned and
+/// dies with the process - cancellation is best-effort.
+fn run_traversal_with_timeout<T, F>(
+    timeout: Duration,
+    output: &Path,
+    traversal: F,
+) -> anyhow::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    match utils::run_with_timeout(timeout, traversal) {
+        Some(result) => result,
+        None => {
+            let _ = fs::remove_file(output);
+            Err(TraversalError::TimedOut(timeout.as_secs_f64()).into())
+        }
+    }
+}
+
+/// Replaces the input paths with the newline-separated path list read from `source`.
+///
+/// Blank lines are ignored; every listed path must exist.
+fn apply_clipboard_inputs(
+    args: &mut RunArgs,
+    source: &mut impl clipboard::ClipboardSource,
+) -> anyhow::Result<()> {
+    let text = source
+        .read_text()
+        .with_context(|| "Failed to read the file list from the clipboard")?;
+
+    let paths: Vec<PathBuf> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect();
+
+    if paths.is_empty() {
+        return Err(anyhow::anyhow!("The clipboard does not contain any paths"));
+    }
+
+    for path in &paths {
+        utils::validate_path_exists(path)
+            .with_context(|| format!("Invalid path on clipboard: {}", path.display()))?;
+    }
+
+    args.input_paths = paths;
+    Ok(())
+}
+
+/// Reads the `--paths-from` list: the named file, or stdin for `-`.
+fn read_path_list(list: &Path) -> anyhow::Result<String> {
+    if list == Path::new("-") {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .with_context(|| "Failed to read the path list from stdin")?;
+        return Ok(text);
+    }
+    fs::read_to_string(list)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: list.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to read path list: {}", list.display()))
+}
+
+/// Adds the newline-separated paths in `text` to the input paths.
+///
+/// They replace the default `.` input rather than bundling the whole directory
+/// alongside them. Blank lines are ignored; every listed path must exist.
+fn apply_listed_inputs(args: &mut RunArgs, text: &str) -> anyhow::Result<()> {
+    let paths: Vec<PathBuf> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect();
+
+    if paths.is_empty() {
+        return Err(anyhow::anyhow!(
+            "The --paths-from list does not contain any paths"
+        ));
+    }
+
+    for path in &paths {
+        utils::validate_path_exists(path)
+            .with_context(|| format!("Invalid path in --paths-from list: {}", path.display()))?;
+    }
+
+    if args.input_paths.is_empty() || args.input_paths == [Path::new(".")] {
+        args.input_paths.clear();
+    }
+    args.input_paths.extend(paths);
+    Ok(())
+}
+
 /// Executes the directory traversal operation.
 fn execute_traversal(
     args: &RunArgs,
     root: &Path,
     input: &Path,
     output: &Path,
-) -> anyhow::Result<()> {
-    println!("\n{}", messages::Messages::starting_adventure());
+    events: Option<&Arc<EventSink>>,
+    sink: Option<&BundleSink>,
+) -> anyhow::Result<walker::TraversalReport> {
+    console::line(format!("\n{}", messages::Messages::starting_adventure()));
 
     if !args.fast_mode {
         animations::animated_dots(&messages::Messages::scanning_files(), 3, 300);
     }
 
-    let walker = walker::Walker::new(root, input, output, &args.exclude);
+    let mut walker = walker::Walker::new(root, input, output, &args.exclude);
+    if let Some(events) = events {
+        walker = walker.with_events(Arc::clone(events));
+    }
+    if let Some(sink) = sink {
+        walker = walker.with_sink(sink.clone());
+    }
 
     if !args.fast_mode {
         let spinner = animations::Spinner::new_tree();
         spinner.spin(&messages::Messages::traversing_tree(), 1200);
     }
 
-    walker.process_dir(args)?;
+    let report = walker.process_dir(args)?;
 
-    println!("\n{}", messages::Messages::gathering_leaves());
+    console::line(format!("\n{}", messages::Messages::gathering_leaves()));
 
-    Ok(())
+    Ok(report)
 }
 
-/// Handles clipboard copy operations.
-fn handle_clipboard(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
-    let mut clip = clipboard::Clipboard::new(output)?;
+/// Truncates the output file (the first part with `--split-tokens`) and writes the optional
+/// `--title` header.
+///
+/// Returns the content statistics of what it wrote, for the traversal to continue.
+/// When the bundle streams into `sink`, the header goes there and the file is left alone.
+///
+/// Walkers always append, so this is what keeps re-runs from stacking onto old output;
+/// with `--append` the file is kept and the header follows its content.
+fn prepare_output(
+    args: &RunArgs,
+    output: &Path,
+    sink: Option<&BundleSink>,
+) -> anyhow::Result<formatter::StatsTally> {
+    let mut tally = formatter::StatsTally::default();
 
-    if args.clipboard {
-        if !args.fast_mode {
-            let spinner = animations::Spinner::new_loading();
-            spinner.spin(&messages::Messages::copying_clipboard(), 800);
-        }
+    // Split bundles are (re)created per group by the walker
+    if args.split_by_dir {
+        return Ok(tally);
+    }
 
-        clip.set_clipboard()?;
-        println!("{}", messages::Messages::clipboard_ready());
+    // Token-split parts are appended to by every input, so drop those of a previous run;
+    // the title then opens the first part
+    let target = if args.split_tokens.is_some() {
+        let mut part = 1;
+        while let path = walker::part_path(output, part)
+            && path.exists()
+        {
+            fs::remove_file(&path)
+                .map_err(|e| FileSystemError::DeleteFailed {
+                    path: path.clone(),
+                    source: e,
+                })
+                .with_context(|| format!("Failed to remove stale part: {}", path.display()))?;
+            part += 1;
+        }
+        walker::part_path(output, 1)
     } else {
-        println!("{}", messages::Messages::clipboard_skipped());
+        output.to_path_buf()
+    };
+
+    let mut header = args
+        .title
+        .as_deref()
+        .map(|title| {
+            let date = (!args.stable_timestamps).then(utils::today_utc);
+            title_header(title, date.as_deref(), args.format)
+        })
+        .unwrap_or_default();
+
+    // One --tree overview of every input's files opens the bundle
+    if args.tree {
+        let plan = plan_inputs(args)?;
+        if !plan.files.is_empty() {
+            if !header.is_empty() {
+                header.push('\n');
+            }
+            header.push_str(&walker::tree_section(
+                args.format,
+                plan.files.iter().map(|file| file.path.as_path()),
+                plan.empty_dirs.iter().map(PathBuf::as_path),
+            ));
+        }
     }
 
-    Ok(())
+    match sink {
+        Some(sink) => sink
+            .clone()
+            .write_all(header.as_bytes())
+            .with_context(|| "Failed to write the title header to stdout")?,
+        None if args.append => append_header(&target, &header)
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: target.clone(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to append to output file: {}", target.display()))?,
+        None => fs::write(&target, &header)
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: target.clone(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to prepare output file: {}", target.display()))?,
+    }
+
+    tally.push(&header);
+    Ok(tally)
 }
 
-/// Shows statistics section with formatted output.
-fn show_stats_section(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
-    if !args.fast_mode {
-        println!("\n{}", messages::Messages::showing_stats());
+/// Appends `header` to `output`, separated from existing content by a blank line.
+fn append_header(output: &Path, header: &str) -> io::Result<()> {
+    let mut file = fs::File::options().append(true).create(true).open(output)?;
+    if !header.is_empty() && file.metadata()?.len() > 0 {
+        file.write_all(b"\n")?;
     }
+    file.write_all(header.as_bytes())
+}
 
-    show_stats(output)?;
+/// Fails if `output` is a symlink, for `--no-follow-output-symlink`.
+fn refuse_symlinked_output(output: &Path) -> anyhow::Result<()> {
+    let symlinked = fs::symlink_metadata(output).is_ok_and(|meta| meta.file_type().is_symlink());
+    if symlinked {
+        return Err(FileSystemError::SymlinkedOutput {
+            path: output.to_path_buf(),
+        }
+        .into());
+    }
     Ok(())
 }
 
-/// Handles editor opening and cleanup operations.
-fn handle_editor(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
-    if args.editor {
-        if !args.fast_mode {
-            println!("\n{}", messages::Messages::opening_editor());
+/// Builds the bundle title header: `=== <title> ===` (a `# <title>` heading for Markdown),
+/// followed by the generation date if given.
+fn title_header(title: &str, date: Option<&str>, format: OutputFormat) -> String {
+    let mut header = match format {
+        OutputFormat::Markdown => format!("# {title}\n"),
+        _ => format!("=== {title} ===\n"),
+    };
+    if let Some(date) = date {
+        // Markdown wants a blank line between the heading and the paragraph below it
+        if format == OutputFormat::Markdown {
+            header.push('\n');
         }
+        header.push_str(&format!("Generated: {date}\n"));
+    }
+    header
+}
 
-        editor::open(output)?;
+/// Applies transformations that need the complete text bundle, rewriting the output file.
+fn finalize_output(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
+    if args.format == OutputFormat::JsonString {
+        wrap_output_as_json_string(output)?;
+    }
 
-        if !args.fast_mode {
-            println!("{}", messages::Messages::editor_opened());
-        }
+    if args.format == OutputFormat::Json {
+        close_json_array(output)?;
+    }
 
-        // Handle file deletion after editor closes
-        if args.delete {
-            if !args.fast_mode {
-                println!("\n{}", messages::Messages::cleaning_up());
-            }
+    if args.no_final_newline {
+        trim_final_newline(output)?;
+    }
 
-            editor::delete(output)?;
+    Ok(())
+}
 
-            if !args.fast_mode {
-                println!("{}", messages::Messages::cleaned_up());
-            }
+/// Lists the files every input would bundle, in write order, without writing anything.
+fn plan_inputs(args: &RunArgs) -> anyhow::Result<walker::Plan> {
+    let root = args.root.as_ref().unwrap();
+    // With --stdout there's no output file that could be skipped
+    let output = args.output_path.clone().unwrap_or_default();
+
+    let mut plan = walker::Plan::default();
+    // --max-files is shared across inputs, as in the run itself
+    let mut input_args = args.clone();
+    for input in &args.input_paths {
+        let planned = walker::Walker::new(root, input, &output, &args.exclude)
+            .plan(&input_args)
+            .with_context(|| format!("Failed to plan the run for: {}", input.display()))?;
+        if let Some(files_left) = input_args.max_files.as_mut() {
+            *files_left = files_left.saturating_sub(planned.files.len());
+        }
+        plan.files.extend(planned.files);
+        plan.empty_dirs.extend(planned.empty_dirs);
+        if input_args.max_files == Some(0) {
+            break;
         }
     }
+    Ok(plan)
+}
 
+/// Writes the files a run would bundle to `out`, one path per line, for `--dry-run`;
+/// their count and total size follow on the console.
+fn write_dry_run(args: &RunArgs, out: &mut impl Write) -> anyhow::Result<()> {
+    let files = plan_inputs(args)?.files;
+    for file in &files {
+        writeln!(out, "{}", file.path.display())
+            .with_context(|| "Failed to write the dry-run file list to stdout")?;
+    }
+    let total_bytes: u64 = files.iter().map(|file| file.bytes).sum();
+    console::line(messages::Messages::dry_run_summary(
+        files.len(),
+        &utils::format_bytes(total_bytes as usize),
+    ));
     Ok(())
 }
 
-/// Displays content statistics for the output file.
-fn show_stats(output: &Path) -> anyhow::Result<()> {
-    use colored::Colorize;
-
-    let content = fs::read_to_string(output)?;
-    let lines = content.split('\n').count();
-    let chars = content.chars().count();
-    let words = content.split_whitespace().count();
-    let bytes = content.len();
+/// Writes the files a run would bundle to `out` as one JSON object, for `--dry-run-json`.
+fn write_dry_run_json(args: &RunArgs, out: &mut impl Write) -> anyhow::Result<()> {
+    let files = plan_inputs(args)?.files;
+    let total_bytes: u64 = files.iter().map(|file| file.bytes).sum();
+    let entries: Vec<_> = files
+        .iter()
+        .map(|file| {
+            serde_json::json!({
+                "path": file.path.display().to_string(),
+                "bytes": file.bytes,
+            })
+        })
+        .collect();
+    let preview = serde_json::json!({
+        "files": entries,
+        "file_count": files.len(),
+        "total_bytes": total_bytes,
+    });
 
-    let stats = formatter::StatsBox::new(lines, chars, words, bytes);
-    println!("{}", stats.render().bright_cyan());
+    writeln!(out, "{}", serde_json::to_string_pretty(&preview)?)
+        .with_context(|| "Failed to write the dry-run preview to stdout")?;
+    Ok(())
+}
 
-    let (emoji, message) = stats.get_size_message();
-    println!("  {emoji} {message}");
+/// Writes the `--index-out` sidecar: a JSON array of `{path, offset, length}` entries.
+fn write_file_index(index_path: &Path, spans: &[walker::FileSpan]) -> anyhow::Result<()> {
+    let entries: Vec<_> = spans
+        .iter()
+        .map(|span| {
+            serde_json::json!({
+                "path": span.path,
+                "offset": span.offset,
+                "length": span.length,
+            })
+        })
+        .collect();
+    let index = serde_json::to_string_pretty(&entries)?;
 
+    fs::write(index_path, index + "\n")
+        .map_err(|e| FileSystemError::WriteFailed {
+            path: index_path.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to write file index: {}", index_path.display()))?;
     Ok(())
 }
 
-/// Logs the current configuration settings to stdout.
-#[rustfmt::skip]
-fn log_config(args: &RunArgs) -> anyhow::Result<()> {
-    let (root, inputs, output) = (
-        args.root.as_ref(),
-        &args.input_paths,
-        args.output_path.as_ref(),
-    );
+/// Opens the `--events` stream: `--events-out` if given, stderr otherwise.
+fn open_events(args: &RunArgs) -> anyhow::Result<Option<Arc<EventSink>>> {
+    let Some(EventFormat::Ndjson) = args.events else {
+        return Ok(None);
+    };
+    let sink = match &args.events_out {
+        Some(path) => EventSink::create(path)?,
+        None => EventSink::stderr(),
+    };
+    Ok(Some(Arc::new(sink)))
+}
 
-    println!(
-        "{}",
-        formatter::ConfigFormatter::format_section_header("Paths to traverse", "📂")
-    );
-    for path in inputs {
-        println!(
-            "{}",
-            formatter::ConfigFormatter::format_list_item("▸", &path.display().to_string())
-        );
-    }
-    println!();
-    println!(
-        "{}",
-        formatter::ConfigFormatter::format_section_header("Configuration Settings", "🔧")
-    );
-    let config_items = vec![
-        ("🌍", "Root Path", formatter::ConfigFormatter::format_path(root.expect("root path must be supplied"))),
-        ("💾", "Output Path", formatter::ConfigFormatter::format_path(output.expect("output path must be supplied"))),
-        ("✏️", "Editor", formatter::ConfigFormatter::format_bool(args.editor)),
-        ("🗑️", "Cleanup", formatter::ConfigFormatter::format_bool(args.delete)),
-        ("📋", "Clipboard", formatter::ConfigFormatter::format_bool(args.clipboard)),
-        ("📊", "Stats", formatter::ConfigFormatter::format_bool(args.stats)),
-        ("👻", "Skip Hidden", formatter::ConfigFormatter::format_bool(args.skip_hidden)),
-        ("⚡", "Fast Mode", formatter::ConfigFormatter::format_bool(args.fast_mode)),
-    ];
+/// Rewrites the output file as a JSON document holding the text bundle.
+fn wrap_output_as_json_string(output: &Path) -> anyhow::Result<()> {
+    let bundle = fs::read_to_string(output)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: output.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| {
+            format!(
+                "Failed to read bundle for JSON wrapping: {}",
+                output.display()
+            )
+        })?;
+
+    fs::write(output, wrap_bundle_as_json_string(&bundle))
+        .map_err(|e| FileSystemError::WriteFailed {
+            path: output.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to write JSON bundle to: {}", output.display()))?;
+
+    Ok(())
+}
+
+/// Ends the `--format json` array the walkers opened and filled with file elements.
+fn close_json_array(output: &Path) -> anyhow::Result<()> {
+    fs::File::options()
+        .append(true)
+        .open(output)
+        .and_then(|mut file| {
+            // No file opened the array (--allow-empty found nothing)
+            let closing: &[u8] = if file.metadata()?.len() == 0 {
+                b"[]\n"
+            } else {
+                b"\n]\n"
+            };
+            file.write_all(closing)
+        })
+        .map_err(|e| FileSystemError::WriteFailed {
+            path: output.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to write JSON bundle to: {}", output.display()))?;
+    Ok(())
+}
+
+/// Returns the first flag set in `args` that a `--format json` array can't represent.
+fn json_format_conflict(args: &RunArgs) -> Option<&'static str> {
+    [
+        (args.title.is_some(), "--title"),
+        (args.tree, "--tree"),
+        (!args.merge_ext.is_empty(), "--merge-ext"),
+        (args.as_patch, "--as-patch"),
+        (args.base64, "--base64"),
+        (args.include_binary.is_some(), "--include-binary"),
+        (args.max_total_lines.is_some(), "--max-total-lines"),
+        (args.split_by_dir, "--split-by-dir"),
+        (args.split_tokens.is_some(), "--split-tokens"),
+        (args.git_meta, "--git-meta"),
+        (args.per_file_tokens, "--per-file-tokens"),
+        (args.line_numbers, "--line-numbers"),
+        (args.dir_headers, "--dir-headers"),
+    ]
+    .into_iter()
+    .find_map(|(set, flag)| set.then_some(flag))
+}
+
+/// Decides whether decorative output is colored: not with `--no-color`, a non-empty
+/// `NO_COLOR`, or output that doesn't reach a terminal.
+fn color_enabled(args: &RunArgs, no_color_env: Option<&OsStr>, on_terminal: bool) -> bool {
+    !args.no_color && no_color_env.is_none_or(OsStr::is_empty) && on_terminal
+}
+
+/// Removes a single trailing `\n` from the output file, if present.
+fn trim_final_newline(output: &Path) -> anyhow::Result<()> {
+    let trim = || -> io::Result<()> {
+        let mut file = fs::File::options().read(true).write(true).open(output)?;
+        let len = file.metadata()?.len();
+        if len == 0 {
+            return Ok(());
+        }
+
+        let mut last = [0u8];
+        file.seek(io::SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        if last[0] == b'\n' {
+            file.set_len(len - 1)?;
+        }
+        Ok(())
+    };
+
+    trim()
+        .map_err(|e| FileSystemError::WriteFailed {
+            path: output.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to trim final newline of: {}", output.display()))
+}
+
+/// Points the output path at a temp file next to the requested output for `--hash-suffix`.
+///
+/// Returns the temp file (removed if the run fails) and the requested output path.
+fn stage_hashed_output(args: &mut RunArgs) -> anyhow::Result<(NamedTempFile, PathBuf)> {
+    let base = args.output_path.clone().unwrap();
+    let dir = base
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let staging = tempfile::Builder::new()
+        .prefix(".treeclip-")
+        .suffix(".tmp")
+        .tempfile_in(dir)
+        .with_context(|| format!("Failed to create temp file in: {}", dir.display()))?;
+    args.output_path = Some(staging.path().to_path_buf());
+    Ok((staging, base))
+}
+
+/// Renames the staged bundle to `base` with its content hash injected before the extension.
+fn persist_with_hash(staging: NamedTempFile, base: &Path) -> anyhow::Result<PathBuf> {
+    let content = fs::read(staging.path())
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: staging.path().to_path_buf(),
+            source: e,
+        })
+        .with_context(|| "Failed to read the bundle for hashing")?;
+    let hashed = hash_suffixed_path(base, &content);
+
+    staging
+        .persist(&hashed)
+        .map_err(|e| FileSystemError::WriteFailed {
+            path: hashed.clone(),
+            source: e.error,
+        })
+        .with_context(|| format!("Failed to move the bundle to: {}", hashed.display()))?;
+    Ok(hashed)
+}
+
+/// Injects a short content hash before the extension: `bundle.txt` → `bundle-<hash>.txt`.
+fn hash_suffixed_path(base: &Path, content: &[u8]) -> PathBuf {
+    let digest = Sha256::digest(content);
+    let hash: String = digest[..HASH_SUFFIX_BYTES]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    let stem = base.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match base.extension() {
+        Some(extension) => format!("{stem}-{hash}.{}", extension.to_string_lossy()),
+        None => format!("{stem}-{hash}"),
+    };
+    base.with_file_name(name)
+}
+
+/// Returns true if the bundle goes to stdout, via `--stdout` or `-o -`.
+fn writes_to_stdout(args: &RunArgs) -> bool {
+    args.stdout || args.output_path.as_deref() == Some(Path::new(STDOUT_PATH))
+}
+
+/// Points the output path at a fresh scratch directory for `--stdout` mode.
+///
+/// The returned directory must be kept alive until the bundle has been emitted.
+fn stage_stdout_output(args: &mut RunArgs) -> anyhow::Result<tempfile::TempDir> {
+    let staging = tempfile::Builder::new()
+        .prefix("treeclip-")
+        .tempdir()
+        .with_context(|| "Failed to create staging directory for --stdout")?;
+    args.output_path = Some(staging.path().join("treeclip_stdout.txt"));
+    Ok(staging)
+}
+
+/// Checks whether `--stdout` can stream the bundle straight from the walkers.
+///
+/// Anything that needs the finished bundle as a whole (wrapping it, trimming its end,
+/// hashing, highlighting, copying, stats, or an editor) has it staged in a file instead.
+fn streams_directly(args: &RunArgs) -> bool {
+    args.stdout
+        && args.format != OutputFormat::JsonString
+        && args.format != OutputFormat::Json
+        && !args.no_final_newline
+        && !args.hash_suffix
+        && !args.highlight
+        && !args.clipboard
+        && !args.stats
+        && !args.editor
+}
+
+/// Checks whether `--highlight` applies: only a text bundle going straight to a terminal.
+///
+/// Files and the clipboard never get highlighted, so no escape codes end up in a saved bundle.
+fn should_highlight(args: &RunArgs, target_is_terminal: bool) -> bool {
+    args.highlight && args.stdout && args.format == OutputFormat::Text && target_is_terminal
+}
+
+/// Copies the finished bundle into `sink` (stdout in normal operation), optionally highlighted.
+fn emit_bundle(output: &Path, sink: &mut impl Write, highlight: bool) -> anyhow::Result<()> {
+    let mut bundle = fs::File::open(output)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: output.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to read bundle for stdout: {}", output.display()))?;
+
+    if highlight {
+        let mut content = String::new();
+        bundle
+            .read_to_string(&mut content)
+            .map_err(|e| FileSystemError::ReadFailed {
+                path: output.to_path_buf(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to read bundle for stdout: {}", output.display()))?;
+        sink.write_all(highlight::highlight_bundle(&content).as_bytes())
+            .with_context(|| "Failed to write bundle to stdout")?;
+    } else {
+        io::copy(&mut bundle, sink).with_context(|| "Failed to write bundle to stdout")?;
+    }
+    sink.flush().with_context(|| "Failed to flush stdout")?;
+    Ok(())
+}
+
+/// Wraps the whole text bundle into a single JSON object: `{"bundle":"..."}`.
+fn wrap_bundle_as_json_string(bundle: &str) -> String {
+    serde_json::json!({ "bundle": bundle }).to_string()
+}
+
+/// Handles clipboard copy operations.
+fn handle_clipboard(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
+    if !args.clipboard {
+        console::line(messages::Messages::clipboard_skipped());
+        return Ok(());
+    }
+
+    if !args.fast_mode {
+        let spinner = animations::Spinner::new_loading();
+        spinner.spin(&messages::Messages::copying_clipboard(), 800);
+    }
+
+    match args.clipboard_backend {
+        clipboard::ClipboardBackend::System => {
+            if args.compress_clipboard {
+                console::warning(messages::Messages::compress_clipboard_ignored());
+            }
+
+            // Only touch the system clipboard when it's actually requested (headless CI has none)
+            let mut clip = clipboard::Clipboard::new(output)?.with_wait(clipboard_wait(args));
+            clip.set_clipboard_as(resolve_clipboard_mime(args))?;
+        }
+        clipboard::ClipboardBackend::Osc52 => {
+            let content = fs::read_to_string(output)
+                .map_err(|e| FileSystemError::ReadFailed {
+                    path: output.to_path_buf(),
+                    source: e,
+                })
+                .with_context(|| {
+                    format!("Failed to read bundle for OSC 52: {}", output.display())
+                })?;
+            clipboard::osc52::copy(&content, args.compress_clipboard)
+                .with_context(|| "Failed to build OSC 52 clipboard sequence")?;
+        }
+    }
+
+    console::line(messages::Messages::clipboard_ready());
+    Ok(())
+}
+
+/// Places the plain-text statistics on the clipboard through the configured backend.
+fn copy_stats_to_clipboard(args: &RunArgs, stats: &formatter::StatsBox) -> anyhow::Result<()> {
+    match args.clipboard_backend {
+        clipboard::ClipboardBackend::System => {
+            write_stats(&mut clipboard::system_target(clipboard_wait(args))?, stats)?;
+        }
+        clipboard::ClipboardBackend::Osc52 => {
+            clipboard::osc52::copy(&stats.render_plain(), false)
+                .with_context(|| "Failed to build OSC 52 clipboard sequence")?;
+        }
+    }
+
+    console::line(messages::Messages::stats_copied());
+    Ok(())
+}
+
+/// How long to hold the system clipboard after setting it: `--clipboard-wait-ms`, or the default.
+fn clipboard_wait(args: &RunArgs) -> Duration {
+    args.clipboard_wait_ms
+        .map_or(clipboard::DEFAULT_OWNERSHIP_WAIT, Duration::from_millis)
+}
+
+/// Writes the plain-text statistics to `target`.
+fn write_stats(
+    target: &mut impl clipboard::ClipboardTarget,
+    stats: &formatter::StatsBox,
+) -> anyhow::Result<()> {
+    target
+        .write_text(&stats.render_plain())
+        .with_context(|| "Failed to copy the statistics to the clipboard")
+}
+
+/// Resolves `--clipboard-format` or the `--clipboard-mime` hint, falling back to plain text
+/// when the hint can't be honored.
+fn resolve_clipboard_mime(args: &RunArgs) -> clipboard::ClipboardMime {
+    if args.clipboard_format == clipboard::ClipboardFormat::Html {
+        return match args.format {
+            OutputFormat::Markdown => clipboard::ClipboardMime::RenderedMarkdown,
+            _ => clipboard::ClipboardMime::Html,
+        };
+    }
+
+    let Some(hint) = args.clipboard_mime.as_deref() else {
+        return clipboard::ClipboardMime::PlainText;
+    };
+
+    clipboard::ClipboardMime::from_hint(hint).unwrap_or_else(|| {
+        console::warning(messages::Messages::clipboard_mime_fallback(hint));
+        clipboard::ClipboardMime::PlainText
+    })
+}
+
+/// Shows statistics section with formatted output.
+///
+/// Uses the statistics tallied while writing, unless the bundle was rewritten afterwards.
+fn show_stats_section(
+    args: &RunArgs,
+    output: &Path,
+    tallied: formatter::StatsTally,
+) -> anyhow::Result<()> {
+    if !args.fast_mode {
+        console::line(format!("\n{}", messages::Messages::showing_stats()));
+    }
+
+    if tally_matches_output(args) {
+        show_stats_box(tallied.stats(args.word_mode));
+    } else {
+        show_stats(output, args.word_mode)?;
+    }
+    Ok(())
+}
+
+/// Computes the bundle's statistics: the tallied ones, unless the bundle was rewritten afterwards.
+fn bundle_stats(
+    args: &RunArgs,
+    output: &Path,
+    tallied: formatter::StatsTally,
+) -> anyhow::Result<formatter::StatsBox> {
+    if tally_matches_output(args) {
+        return Ok(tallied.stats(args.word_mode));
+    }
+
+    let content = fs::read_to_string(output)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: output.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to read bundle for stats: {}", output.display()))?;
+    Ok(formatter::StatsBox::from_content(&content, args.word_mode))
+}
+
+/// Checks whether the bundle on disk is exactly what was written (and tallied) during traversal.
+fn tally_matches_output(args: &RunArgs) -> bool {
+    !matches!(args.format, OutputFormat::JsonString | OutputFormat::Json)
+        && !args.no_final_newline
+        && args.split_tokens.is_none()
+        && !args.append
+}
+
+/// Handles editor opening and cleanup operations.
+fn handle_editor(args: &RunArgs, output: &Path) -> anyhow::Result<()> {
+    if args.editor {
+        if !args.fast_mode {
+            console::line(format!("\n{}", messages::Messages::opening_editor()));
+        }
+
+        editor::open(output, args.editor_cmd.as_deref(), &args.fallback_editors)?;
+
+        if !args.fast_mode {
+            console::line(messages::Messages::editor_opened());
+        }
+
+        // Handle file deletion after editor closes
+        if args.delete {
+            if !args.fast_mode {
+                console::line(format!("\n{}", messages::Messages::cleaning_up()));
+            }
+
+            editor::delete(output)?;
+
+            if !args.fast_mode {
+                console::line(messages::Messages::cleaned_up());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the first `lines` lines of the bundle, for `--preview`.
+fn show_preview(output: &Path, lines: usize) -> anyhow::Result<()> {
+    let file = fs::File::open(output)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: output.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to open bundle for preview: {}", output.display()))?;
+
+    console::line(format!("\n{}", messages::Messages::preview_heading(lines)));
+    for line in io::BufReader::new(file).lines().take(lines) {
+        let line = line
+            .with_context(|| format!("Failed to read bundle for preview: {}", output.display()))?;
+        console::line(line);
+    }
+    Ok(())
+}
+
+/// Displays content statistics for the output file.
+fn show_stats(output: &Path, word_mode: WordMode) -> anyhow::Result<()> {
+    let content = fs::read_to_string(output)?;
+    show_content_stats(&content, word_mode);
+    Ok(())
+}
+
+/// Displays the statistics box and size feedback for a bundle's content.
+pub(super) fn show_content_stats(content: &str, word_mode: WordMode) {
+    show_stats_box(formatter::StatsBox::from_content(content, word_mode));
+}
+
+/// Displays a statistics box and the size feedback that goes with it.
+pub(super) fn show_stats_box(stats: formatter::StatsBox) {
+    use colored::Colorize;
+
+    console::line(stats.render().bright_cyan());
+
+    let (emoji, message) = stats.get_size_message();
+    console::line(format!("  {emoji} {message}"));
+}
+
+/// Displays per-pattern match counts, flagging patterns that never matched.
+fn show_pattern_stats(stats: &exclude::PatternStats) {
+    use colored::Colorize;
+
+    console::line(formatter::ConfigFormatter::format_section_header(
+        "Exclusion Pattern Usage",
+        "🧮",
+    ));
+    if stats.entries().is_empty() {
+        console::line(formatter::ConfigFormatter::format_list_item(
+            "▸",
+            "No exclusion patterns configured",
+        ));
+        return;
+    }
+
+    for entry in stats.entries() {
+        let count = if entry.matches == 0 {
+            "unused".yellow().bold()
+        } else {
+            format!("{} matched", entry.matches).green()
+        };
+        console::line(format!(
+            "  {} {:<12} {} {}",
+            "▸".dimmed(),
+            count,
+            entry.pattern.bright_white(),
+            format!("({})", entry.source).dimmed()
+        ));
+    }
+
+    let unused = stats.unused().count();
+    if unused > 0 {
+        console::line(format!(
+            "  {} {}",
+            "💡".yellow(),
+            format!("{unused} unused pattern(s) could be pruned").dimmed()
+        ));
+    }
+}
+
+/// Prints a human-readable summary of what the run will do.
+fn explain_plan(args: &RunArgs) -> anyhow::Result<()> {
+    let root = args.root.as_ref().unwrap();
+    let output = args.output_path.as_ref().unwrap();
+
+    console::line(formatter::ConfigFormatter::format_section_header(
+        "Plan", "🧭",
+    ));
+    for input in &args.input_paths {
+        let plan = walker::Walker::new(root, input, output, &args.exclude)
+            .explain(args)
+            .with_context(|| format!("Failed to explain the plan for: {}", input.display()))?;
+
+        console::line(format!(
+            "  {} {} {}",
+            "▸".dimmed(),
+            input.display().to_string().cyan().bold(),
+            format!("(~{} files)", plan.estimated_files).dimmed()
+        ));
+        if plan.excludes.is_empty() {
+            console::line(formatter::ConfigFormatter::format_list_item(
+                "  ·",
+                "no exclusions",
+            ));
+        }
+        for exclude in &plan.excludes {
+            console::line(formatter::ConfigFormatter::format_list_item(
+                "  ·",
+                &format!("exclude {} (from {})", exclude.pattern, exclude.source),
+            ));
+        }
+    }
+
+    let destination = if args.stdout {
+        "stdout".to_string()
+    } else {
+        output.display().to_string()
+    };
+    let format = args
+        .format
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default();
+    console::line(formatter::ConfigFormatter::format_config_line(
+        "💾",
+        "Output",
+        destination.cyan().bold(),
+    ));
+    console::line(formatter::ConfigFormatter::format_config_line(
+        "📄",
+        "Format",
+        format.cyan().bold(),
+    ));
+    Ok(())
+}
+
+/// Prints the file size histogram.
+fn show_size_histogram(file_sizes: &[u64]) {
+    console::line(formatter::ConfigFormatter::format_section_header(
+        "File Sizes",
+        "📦",
+    ));
+    console::line(formatter::SizeHistogram::from_sizes(file_sizes).render());
+}
+
+/// Prints how many bundled files are in each language.
+fn show_language_report(file_paths: &[PathBuf]) {
+    console::line(
+        formatter::LanguageReport::from_paths(file_paths.iter().map(PathBuf::as_path)).render(),
+    );
+}
+
+/// Logs the current configuration settings to stdout.
+#[rustfmt::skip]
+fn log_config(args: &RunArgs) -> anyhow::Result<()> {
+    let (root, inputs, output) = (
+        args.root.as_ref(),
+        &args.input_paths,
+        args.output_path.as_ref(),
+    );
+
+    console::line(formatter::ConfigFormatter::format_section_header("Paths to traverse", "📂"));
+    for path in inputs {
+        console::line(formatter::ConfigFormatter::format_list_item("▸", &path.display().to_string()));
+    }
+    console::line("");
+    console::line(formatter::ConfigFormatter::format_section_header("Configuration Settings", "🔧"));
+    let config_items = vec![
+        ("🌍", "Root Path", formatter::ConfigFormatter::format_path(root.expect("root path must be supplied"))),
+        ("💾", "Output Path", formatter::ConfigFormatter::format_path(output.expect("output path must be supplied"))),
+        ("✏️", "Editor", formatter::ConfigFormatter::format_bool(args.editor)),
+        ("🗑️", "Cleanup", formatter::ConfigFormatter::format_bool(args.delete)),
+        ("📋", "Clipboard", formatter::ConfigFormatter::format_bool(args.clipboard)),
+        ("📊", "Stats", formatter::ConfigFormatter::format_bool(args.stats)),
+        ("👻", "Skip Hidden", formatter::ConfigFormatter::format_bool(args.skip_hidden)),
+        ("⚡", "Fast Mode", formatter::ConfigFormatter::format_bool(args.fast_mode)),
+    ];
 
     for (icon, label, value) in config_items {
-        println!(
-            "{}",
-            formatter::ConfigFormatter::format_config_line(icon, label, value)
-        );
+        console::line(formatter::ConfigFormatter::format_config_line(icon, label, value));
     }
 
     if !args.exclude.is_empty() {
-        println!(
-            "{}",
-            formatter::ConfigFormatter::format_section_header("Excluded Patterns", "🚫")
-        );
+        console::line(formatter::ConfigFormatter::format_section_header("Excluded Patterns", "🚫"));
         for pattern in &args.exclude {
-            println!(
-                "{}",
-                formatter::ConfigFormatter::format_list_item("▸", pattern)
-            );
+            console::line(formatter::ConfigFormatter::format_list_item("▸", pattern));
         }
     }
 
-    println!("{}", messages::Messages::ready_to_launch());
+    console::line(messages::Messages::ready_to_launch());
     Ok(())
 }
 
@@ -286,6 +1457,7 @@ mod run_tests {
             skip_hidden: true,
             raw: true,
             fast_mode: false,
+            ..Default::default()
         };
 
         normalize_paths(&mut args)?;
@@ -297,6 +1469,1615 @@ mod run_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_resolve_clipboard_mime() {
+        let mut args = RunArgs {
+            clipboard: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_clipboard_mime(&args),
+            clipboard::ClipboardMime::PlainText
+        );
+
+        args.clipboard_mime = Some("text/html".to_string());
+        assert_eq!(
+            resolve_clipboard_mime(&args),
+            clipboard::ClipboardMime::Html
+        );
+
+        // Unsupported hints fall back to plain text
+        args.clipboard_mime = Some("text/markdown".to_string());
+        assert_eq!(
+            resolve_clipboard_mime(&args),
+            clipboard::ClipboardMime::PlainText
+        );
+
+        // --clipboard-format html renders Markdown bundles
+        args.clipboard_mime = None;
+        args.clipboard_format = clipboard::ClipboardFormat::Html;
+        assert_eq!(
+            resolve_clipboard_mime(&args),
+            clipboard::ClipboardMime::Html
+        );
+        args.format = OutputFormat::Markdown;
+        assert_eq!(
+            resolve_clipboard_mime(&args),
+            clipboard::ClipboardMime::RenderedMarkdown
+        );
+    }
+
+    #[test]
+    fn test_finalize_output_json_string_round_trips() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(
+            input.join("main.rs"),
+            "fn main() {\n\tprintln!(\"hi \\\"there\\\"\");\n}\n",
+        )?;
+        fs::write(input.join("notes.md"), "# Notes\r\nunicode: ✨")?;
+
+        let mut args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        let text_output = temp_dir.path().join("bundle.txt");
+        walker::Walker::new(temp_dir.path(), &input, &text_output, &[]).process_dir(&args)?;
+        let text_bundle = fs::read_to_string(&text_output)?;
+
+        args.format = OutputFormat::JsonString;
+        let json_output = temp_dir.path().join("bundle.json");
+        walker::Walker::new(temp_dir.path(), &input, &json_output, &[]).process_dir(&args)?;
+        finalize_output(&args, &json_output)?;
+
+        let parsed: serde_json::Value = serde_json::from_str(&fs::read_to_string(&json_output)?)?;
+        assert_eq!(parsed["bundle"].as_str(), Some(text_bundle.as_str()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_format_lists_each_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(&input)?;
+        fs::write(
+            input.join("main.rs"),
+            "fn main() {\n    println!(\"\\\"hi\\\"\");\n}\n",
+        )?;
+        fs::write(input.join("notes.txt"), "ünïcödé\n")?;
+        fs::write(input.join("logo.png"), [0x89, b'P', b'N', b'G', 0, 0, 0xff])?;
+        fs::write(input.join("vendor.js"), "minified();\n")?;
+
+        let output = temp_dir.path().join("bundle.json");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(input.clone()),
+            format: OutputFormat::Json,
+            stub: vec!["vendor.js".to_string()],
+            fast_mode: true,
+            ..Default::default()
+        };
+        execute_with_sink(
+            args,
+            BundleSink::new(io::sink()),
+            &mut banner::decoration_rng(None),
+        )?;
+
+        let parsed: serde_json::Value = serde_json::from_str(&fs::read_to_string(&output)?)?;
+        let files = parsed.as_array().unwrap();
+        let paths: Vec<_> = files.iter().map(|file| file["path"].clone()).collect();
+        assert_eq!(paths, ["logo.png", "main.rs", "notes.txt", "vendor.js"]);
+        for file in files {
+            let path = input.join(file["path"].as_str().unwrap());
+            assert_eq!(file["bytes"], fs::metadata(&path)?.len());
+        }
+
+        assert_eq!(files[0]["binary"], true);
+        assert!(files[0].get("content").is_none());
+        // Content is kept exactly, escapes and trailing newline included
+        assert_eq!(
+            files[1]["content"],
+            fs::read_to_string(input.join("main.rs"))?
+        );
+        assert_eq!(files[2]["content"], "ünïcödé\n");
+        // Stubbed files are flagged rather than given placeholder content
+        assert_eq!(files[3]["stub"], true);
+        assert!(files[3].get("content").is_none());
+        assert!(files[1].get("stub").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_color_disables_color() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut args = RunArgs {
+            input_paths: vec![temp_dir.path().to_path_buf()],
+            output_path: Some(temp_dir.path().join("out.txt")),
+            root: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        assert!(color_enabled(&args, None, true));
+        assert!(color_enabled(&args, Some(OsStr::new("")), true));
+        assert!(!color_enabled(&args, Some(OsStr::new("1")), true));
+        assert!(!color_enabled(&args, None, false));
+        args.no_color = true;
+        assert!(!color_enabled(&args, None, true));
+        Ok(())
+    }
+
+    #[test]
+    fn test_allow_empty_writes_empty_bundle() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("empty");
+        fs::create_dir_all(&input)?;
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(input.clone()),
+            fast_mode: true,
+            ..Default::default()
+        };
+        let run = |args: RunArgs| {
+            console::capture::run(|| {
+                execute_with_sink(
+                    args,
+                    BundleSink::new(io::sink()),
+                    &mut banner::decoration_rng(None),
+                )
+            })
+            .0
+        };
+
+        let err = run(args.clone()).unwrap_err();
+        assert!(format!("{err:#}").contains("No files found"));
+
+        run(RunArgs {
+            allow_empty: true,
+            ..args.clone()
+        })?;
+        assert_eq!(fs::read_to_string(&output)?, "");
+
+        // A JSON bundle is still a valid (empty) array
+        run(RunArgs {
+            allow_empty: true,
+            format: OutputFormat::Json,
+            ..args
+        })?;
+        let parsed: serde_json::Value = serde_json::from_str(&fs::read_to_string(&output)?)?;
+        assert_eq!(parsed, serde_json::json!([]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_accumulates_runs() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let (api, web, empty) = (
+            temp_dir.path().join("api"),
+            temp_dir.path().join("web"),
+            temp_dir.path().join("empty"),
+        );
+        for dir in [&api, &web, &empty] {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(api.join("server.rs"), "fn serve() {}\n")?;
+        fs::write(web.join("app.ts"), "export {};\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let run = |input: &Path| {
+            let args = RunArgs {
+                input_paths: vec![input.to_path_buf()],
+                output_path: Some(output.clone()),
+                root: Some(input.to_path_buf()),
+                append: true,
+                fast_mode: true,
+                ..Default::default()
+            };
+            console::capture::run(|| {
+                execute_with_sink(
+                    args,
+                    BundleSink::new(io::sink()),
+                    &mut banner::decoration_rng(None),
+                )
+            })
+            .0
+        };
+
+        run(&api)?;
+        run(&web)?;
+        let bundle = "==> server.rs\nfn serve() {}\n\n==> app.ts\nexport {};\n";
+        assert_eq!(fs::read_to_string(&output)?, bundle);
+
+        // Nothing new to add, but the existing bundle stays and the run succeeds
+        run(&empty)?;
+        assert_eq!(fs::read_to_string(&output)?, bundle);
+        Ok(())
+    }
+
+    #[test]
+    fn test_preview_prints_first_lines() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(&input)?;
+        fs::write(input.join("a.rs"), "fn a() {}\nfn b() {}\n")?;
+        fs::write(input.join("b.rs"), "fn c() {}\n")?;
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(temp_dir.path().join("bundle.txt")),
+            root: Some(input.clone()),
+            fast_mode: true,
+            preview: true,
+            preview_lines: Some(4),
+            ..Default::default()
+        };
+
+        let (result, captured) = console::capture::run(|| {
+            execute_with_sink(
+                args,
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )
+        });
+        result?;
+        let (_, preview) = captured
+            .stdout
+            .split_once("First 4 lines of the bundle:\n")
+            .unwrap();
+        assert!(preview.starts_with("==> a.rs\nfn a() {}\nfn b() {}\n\n"));
+        assert!(!preview.contains("b.rs"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_warns_when_every_file_is_stubbed() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+        fs::write(input.join("empty.rs"), "\n")?;
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(temp_dir.path().join("bundle.txt")),
+            root: Some(input.clone()),
+            fast_mode: true,
+            ..Default::default()
+        };
+        let run = |args: RunArgs| {
+            console::capture::run(|| {
+                execute_with_sink(
+                    args,
+                    BundleSink::new(io::sink()),
+                    &mut banner::decoration_rng(None),
+                )
+            })
+        };
+
+        let (result, captured) = run(args.clone());
+        result?;
+        assert!(!captured.stderr.contains("are empty, stubbed, or skipped"));
+
+        let (result, captured) = run(RunArgs {
+            stub: vec!["main.rs".into()],
+            ..args
+        });
+        result?;
+        assert!(
+            captured
+                .stderr
+                .contains("All 2 bundled files are empty, stubbed, or skipped")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_quiet_run_prints_nothing() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+        fs::write(input.join(".treeclipignore"), "*.log\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(input.clone()),
+            quiet: true,
+            stats: true,
+            ..Default::default()
+        };
+        let (result, captured) = console::capture::run(|| {
+            execute_with_sink(
+                args,
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )
+        });
+        result?;
+
+        assert!(captured.stdout.is_empty(), "{}", captured.stdout);
+        assert!(captured.stderr.is_empty(), "{}", captured.stderr);
+        assert!(fs::read_to_string(&output)?.contains("fn main() {}"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_format_rejects_title() {
+        let mut args = RunArgs {
+            input_paths: vec![PathBuf::from(".")],
+            format: OutputFormat::Json,
+            title: Some("Backend".to_string()),
+            ..Default::default()
+        };
+        let err = normalize_paths(&mut args).unwrap_err();
+        assert!(err.to_string().contains("--title"));
+    }
+
+    #[test]
+    fn test_size_histogram_buckets_bundled_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("tiny.rs"), "x".repeat(100))?;
+        fs::write(input.join("small.rs"), "x".repeat(500))?;
+        fs::write(input.join("medium.rs"), "x".repeat(5_000))?;
+        fs::write(input.join("large.rs"), "x".repeat(150_000))?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            fast_mode: true,
+            ..Default::default()
+        };
+        let report =
+            walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+
+        let histogram = formatter::SizeHistogram::from_sizes(&report.file_sizes);
+        assert_eq!(histogram.counts(), [2, 1, 0, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_language_report_counts_bundled_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}")?;
+        fs::write(input.join("lib.rs"), "pub fn f() {}")?;
+        fs::write(input.join("tool.py"), "print('hi')")?;
+        fs::write(input.join("notes.unknownext"), "?")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            fast_mode: true,
+            ..Default::default()
+        };
+        let report =
+            walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+
+        let languages =
+            formatter::LanguageReport::from_paths(report.file_paths.iter().map(PathBuf::as_path));
+        assert_eq!(
+            languages.counts(),
+            [("rust", 2), ("other", 1), ("python", 1)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_language_stats_per_extension() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {\n}\n")?;
+        fs::write(input.join("lib.rs"), "pub fn f() {}\n")?;
+        fs::write(input.join("tool.py"), "import os\n\nprint(os.sep)\n\n\n")?;
+        fs::write(input.join("README.md"), "# Hi\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            fast_mode: true,
+            ..Default::default()
+        };
+        let report =
+            walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+
+        let tally = |files, lines, bytes| formatter::LanguageTally {
+            files,
+            lines,
+            bytes,
+        };
+        assert_eq!(
+            report.languages.rows(),
+            [
+                ("py", tally(1, 3, 27)),
+                ("rs", tally(2, 3, 28)),
+                ("md", tally(1, 1, 5)),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_final_newline() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let mut args = RunArgs {
+            input_paths: vec![input.clone()],
+            fast_mode: true,
+            ..Default::default()
+        };
+        walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+        finalize_output(&args, &output)?;
+        assert_eq!(fs::read(&output)?.last(), Some(&b'\n'));
+
+        args.no_final_newline = true;
+        finalize_output(&args, &output)?;
+        let bundle = fs::read(&output)?;
+        assert_eq!(bundle.last(), Some(&b'}'));
+
+        // Only a single newline is ever trimmed
+        finalize_output(&args, &output)?;
+        assert_eq!(fs::read(&output)?, bundle);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stdout_mode_keeps_stdout_clean() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            stdout: true,
+            stats: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        let (sink, bundle) = BundleSink::in_memory();
+        let (result, captured) = console::capture::run(|| {
+            execute_with_sink(args, sink, &mut banner::decoration_rng(None))
+        });
+        result?;
+
+        // Decorative output went to stderr, nothing to stdout
+        assert!(
+            captured.stdout.is_empty(),
+            "stdout polluted: {}",
+            captured.stdout
+        );
+        assert!(!captured.stderr.is_empty());
+
+        let bundle = String::from_utf8(bundle.lock().unwrap().clone())?;
+        assert!(bundle.starts_with("==> "));
+        assert!(bundle.contains("fn main() {}"));
+        assert!(!temp_dir.path().join("treeclip_temp.txt").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stdout_streams_directly_with_title() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            output_path: Some(PathBuf::from("-")),
+            title: Some("Piped".to_string()),
+            stable_timestamps: true,
+            ..Default::default()
+        };
+        assert!(streams_directly(&RunArgs {
+            stdout: true,
+            ..args.clone()
+        }));
+
+        let (sink, bundle) = BundleSink::in_memory();
+        let (result, captured) = console::capture::run(|| {
+            execute_with_sink(args, sink, &mut banner::decoration_rng(None))
+        });
+        result?;
+
+        assert!(captured.stdout.is_empty());
+        assert_eq!(
+            String::from_utf8(bundle.lock().unwrap().clone())?,
+            "=== Piped ===\n\n==> src/main.rs\nfn main() {}\n"
+        );
+
+        // Stats need the finished bundle, so it's staged first
+        assert!(!streams_directly(&RunArgs {
+            stdout: true,
+            stats: true,
+            ..Default::default()
+        }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_lists_input_and_output() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+        fs::write(input.join("debug.log"), "noise\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(temp_dir.path().to_path_buf()),
+            exclude: vec!["*.log".to_string()],
+            ..Default::default()
+        };
+
+        let (result, captured) = console::capture::run(|| explain_plan(&args));
+        result?;
+
+        assert!(captured.stdout.contains(&input.display().to_string()));
+        assert!(captured.stdout.contains(&output.display().to_string()));
+        assert!(captured.stdout.contains("(~1 files)"));
+        assert!(captured.stdout.contains("*.log"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_print_output_path_is_only_stdout_line() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(temp_dir.path().to_path_buf()),
+            print_output_path: true,
+            stats: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        let (sink, stdout) = BundleSink::in_memory();
+        let (result, captured) = console::capture::run(|| {
+            execute_with_sink(args, sink, &mut banner::decoration_rng(None))
+        });
+        result?;
+
+        assert!(
+            captured.stdout.is_empty(),
+            "stdout polluted: {}",
+            captured.stdout
+        );
+        assert!(!captured.stderr.is_empty());
+        assert_eq!(
+            String::from_utf8(stdout.lock().unwrap().clone())?,
+            format!("{}\n", output.display())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedup_inputs_drops_nested_input() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project = temp_dir.path().join("project");
+        let src = project.join("src");
+        fs::create_dir_all(&src)?;
+        fs::write(src.join("main.rs"), "fn main() {}\n")?;
+        fs::write(project.join("README.md"), "# Project\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let mut args = RunArgs {
+            input_paths: vec![src.clone(), project.clone()],
+            output_path: Some(output.clone()),
+            root: Some(temp_dir.path().to_path_buf()),
+            dedup_inputs: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        let ((), captured) = console::capture::run(|| check_overlapping_inputs(&mut args));
+        assert!(captured.stdout.contains("inside"));
+        assert_eq!(args.input_paths, vec![project.clone()]);
+
+        prepare_output(&args, &output, None)?;
+        console::capture::run(|| traverse_inputs(&args, None, None)).0?;
+        let bundle = fs::read_to_string(&output)?;
+        assert_eq!(bundle.matches("==> project/src/main.rs").count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_suffix_names_output_by_content() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(temp_dir.path().to_path_buf()),
+            hash_suffix: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        let run = |args: RunArgs| {
+            console::capture::run(|| {
+                execute_with_sink(
+                    args,
+                    BundleSink::new(io::sink()),
+                    &mut banner::decoration_rng(None),
+                )
+            })
+            .0
+        };
+        run(args.clone())?;
+
+        let bundles: Vec<_> = fs::read_dir(temp_dir.path())?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<Result<_, _>>()?;
+        let expected = hash_suffixed_path(&output, b"==> src/main.rs\nfn main() {}\n");
+        assert!(expected.exists(), "found {bundles:?}");
+        assert!(!output.exists());
+        assert_eq!(bundles.len(), 2); // src/ and the hashed bundle, no temp files left
+
+        // Same content, same name
+        run(args)?;
+        assert_eq!(fs::read_dir(temp_dir.path())?.count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_out_locates_each_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(input.join("core"))?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+        fs::write(input.join("core").join("lib.rs"), "pub mod ✨;\n\n")?;
+        fs::write(input.join("notes.md"), "# Notes\nSome text")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let index_path = temp_dir.path().join("bundle.index.json");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(temp_dir.path().to_path_buf()),
+            title: Some("Indexed".to_string()),
+            stable_timestamps: true,
+            index_out: Some(index_path.clone()),
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        console::capture::run(|| {
+            execute_with_sink(
+                args,
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )
+        })
+        .0?;
+
+        let bundle = fs::read(&output)?;
+        let index: serde_json::Value = serde_json::from_str(&fs::read_to_string(&index_path)?)?;
+        let entries = index.as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+
+        for entry in entries {
+            let path = entry["path"].as_str().unwrap();
+            let offset = entry["offset"].as_u64().unwrap() as usize;
+            let length = entry["length"].as_u64().unwrap() as usize;
+            let section = std::str::from_utf8(&bundle[offset..offset + length])?;
+
+            let content = fs::read_to_string(temp_dir.path().join(path))?;
+            assert_eq!(section, format!("==> {path}\n{}", content.trim_end()));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_events_stream_one_event_per_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(input.join("core"))?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+        fs::write(input.join("core").join("lib.rs"), "pub mod core;\n")?;
+        fs::write(input.join("notes.md"), "# Notes\n")?;
+
+        let events_path = temp_dir.path().join("events.ndjson");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(temp_dir.path().join("bundle.txt")),
+            root: Some(temp_dir.path().to_path_buf()),
+            events: Some(EventFormat::Ndjson),
+            events_out: Some(events_path.clone()),
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        console::capture::run(|| {
+            execute_with_sink(
+                args,
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )
+        })
+        .0?;
+
+        let events = fs::read_to_string(&events_path)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<serde_json::Value>, _>>()?;
+        assert_eq!(events.len(), 4);
+
+        let (done, files) = events.split_last().unwrap();
+        assert_eq!(done, &serde_json::json!({ "event": "done", "count": 3 }));
+        let mut paths: Vec<_> = files
+            .iter()
+            .map(|event| {
+                assert_eq!(event["event"], "file");
+                event["path"].as_str().unwrap_or_default()
+            })
+            .collect();
+        paths.sort_unstable();
+        assert_eq!(paths, ["src/core/lib.rs", "src/main.rs", "src/notes.md"]);
+        let main = files.iter().find(|event| event["path"] == "src/main.rs");
+        assert_eq!(
+            main.map(|event| &event["bytes"]),
+            Some(&serde_json::json!(13))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_highlight_only_for_terminal_stdout() {
+        let args = RunArgs {
+            highlight: true,
+            stdout: true,
+            ..Default::default()
+        };
+        assert!(should_highlight(&args, true));
+        assert!(!should_highlight(&args, false));
+
+        // Writing to a file never highlights, even from a terminal session
+        let to_file = RunArgs {
+            stdout: false,
+            output_path: Some(PathBuf::from("bundle.txt")),
+            ..args.clone()
+        };
+        assert!(!should_highlight(&to_file, true));
+
+        let not_requested = RunArgs {
+            highlight: false,
+            ..args
+        };
+        assert!(!should_highlight(&not_requested, true));
+    }
+
+    #[test]
+    fn test_tallied_stats_match_reread_stats() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let first = temp_dir.path().join("app");
+        let second = temp_dir.path().join("docs");
+        fs::create_dir_all(first.join("src"))?;
+        fs::create_dir(&second)?;
+        fs::write(
+            first.join("src").join("main.rs"),
+            "fn main() {\n    run();\n}\n",
+        )?;
+        fs::write(first.join("Cargo.toml"), "[package]\nname = \"app\"")?;
+        fs::write(
+            second.join("README.md"),
+            "# Docs ✨\n\n\nTrailing   spaces   \n\n",
+        )?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![first, second],
+            output_path: Some(output.clone()),
+            root: Some(temp_dir.path().to_path_buf()),
+            title: Some("Fixture".to_string()),
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        let (result, _) = console::capture::run(|| -> anyhow::Result<_> {
+            Ok((
+                prepare_output(&args, &output, None)?,
+                traverse_inputs(&args, None, None)?,
+            ))
+        });
+        let (mut tally, report) = result?;
+        tally.merge(report.content);
+
+        let reread =
+            formatter::StatsBox::from_content(&fs::read_to_string(&output)?, args.word_mode);
+        assert_eq!(tally.stats(args.word_mode), reread);
+        assert!(tally_matches_output(&args));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dash_output_path_means_stdout() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(PathBuf::from("-")),
+            root: Some(temp_dir.path().to_path_buf()),
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        let (sink, bundle) = BundleSink::in_memory();
+        let (result, captured) = console::capture::run(|| {
+            execute_with_sink(args, sink, &mut banner::decoration_rng(None))
+        });
+        result?;
+
+        assert!(
+            captured.stdout.is_empty(),
+            "stdout polluted: {}",
+            captured.stdout
+        );
+        assert!(String::from_utf8(bundle.lock().unwrap().clone())?.contains("fn main() {}"));
+        assert!(!Path::new("-").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_title_is_first_line_of_output() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        // Stale content from a previous run must not survive
+        fs::write(&output, "stale bundle\n")?;
+
+        let mut args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            title: Some("Backend API".to_string()),
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        prepare_output(&args, &output, None)?;
+        walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+
+        let content = fs::read_to_string(&output)?;
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some("=== Backend API ==="));
+        assert!(
+            lines
+                .next()
+                .is_some_and(|line| line.starts_with("Generated: "))
+        );
+        assert!(content.contains("==> src/main.rs"));
+        assert!(!content.contains("stale bundle"));
+
+        args.stable_timestamps = true;
+        prepare_output(&args, &output, None)?;
+        assert_eq!(fs::read_to_string(&output)?, "=== Backend API ===\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_title_is_a_heading() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let output = temp_dir.path().join("bundle.md");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            title: Some("Backend API".to_string()),
+            format: OutputFormat::Markdown,
+            stable_timestamps: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+        prepare_output(&args, &output, None)?;
+        walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+
+        let content = fs::read_to_string(&output)?;
+        assert!(content.starts_with("# Backend API\n\n## `src/main.rs`\n"));
+        assert!(!content.contains("==="));
+
+        assert_eq!(
+            title_header("Backend API", Some("2024-06-01"), OutputFormat::Markdown),
+            "# Backend API\n\nGenerated: 2024-06-01\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dry_run_json_lists_planned_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("project");
+        fs::create_dir_all(input.join("src"))?;
+        fs::create_dir_all(input.join("target"))?;
+        fs::write(input.join("src/main.rs"), "fn main() {}\n")?;
+        fs::write(input.join("src/lib.rs"), "pub fn f() {}\n")?;
+        fs::write(input.join("Cargo.toml"), "[package]\n")?;
+        fs::write(input.join("target/build.log"), "excluded\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(input.clone()),
+            exclude: vec!["target".to_string()],
+            dry_run_json: true,
+            ..Default::default()
+        };
+        let (sink, preview) = BundleSink::in_memory();
+        execute_with_sink(args, sink, &mut banner::decoration_rng(None))?;
+
+        let preview: serde_json::Value = serde_json::from_slice(&preview.lock().unwrap().clone())?;
+        assert_eq!(preview["file_count"], 3);
+        assert_eq!(preview["total_bytes"], 13 + 14 + 10);
+        let paths: Vec<_> = preview["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|file| file["path"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(paths, ["Cargo.toml", "src/lib.rs", "src/main.rs"]);
+        assert!(!output.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dry_run_lists_files_without_writing() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("project");
+        fs::create_dir_all(input.join("src"))?;
+        fs::create_dir_all(input.join("target"))?;
+        fs::write(input.join("src/main.rs"), "fn main() {}\n")?;
+        fs::write(input.join("README.md"), "# Project\n")?;
+        fs::write(input.join("target/build.log"), "excluded\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(input.clone()),
+            exclude: vec!["target".to_string()],
+            clipboard: true,
+            editor: true,
+            fast_mode: true,
+            dry_run: true,
+            ..Default::default()
+        };
+        let (sink, list) = BundleSink::in_memory();
+        let (result, captured) = console::capture::run(|| {
+            execute_with_sink(args.clone(), sink, &mut banner::decoration_rng(None))
+        });
+        result?;
+        assert!(!output.exists());
+
+        let planned: Vec<_> = walker::Walker::new(&input, &input, &output, &args.exclude)
+            .plan(&args)?
+            .files
+            .into_iter()
+            .map(|file| file.path.display().to_string())
+            .collect();
+        assert_eq!(planned, ["README.md", "src/main.rs"]);
+        let list = String::from_utf8(list.lock().unwrap().clone())?;
+        assert_eq!(list.lines().collect::<Vec<_>>(), planned);
+        assert!(captured.stdout.is_empty());
+        assert!(captured.stderr.contains("2 files would be bundled"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_runs_before_dry_run() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(temp_dir.path().join("main.rs"), "fn main() {}\n")?;
+
+        let args = RunArgs {
+            input_paths: vec![temp_dir.path().to_path_buf()],
+            root: Some(temp_dir.path().to_path_buf()),
+            explain: true,
+            dry_run: true,
+            ..Default::default()
+        };
+        let (result, captured) = console::capture::run(|| {
+            execute_with_sink(
+                args,
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )
+        });
+        result?;
+        assert!(captured.stderr.contains("Plan"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dry_run_list_survives_quiet_and_stdout() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("project");
+        fs::create_dir_all(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        for (quiet, stdout) in [(true, false), (false, true)] {
+            let args = RunArgs {
+                input_paths: vec![input.clone()],
+                root: Some(input.clone()),
+                quiet,
+                stdout,
+                dry_run: true,
+                ..Default::default()
+            };
+            let (sink, list) = BundleSink::in_memory();
+            let (result, captured) = console::capture::run(|| {
+                execute_with_sink(args, sink, &mut banner::decoration_rng(None))
+            });
+            result?;
+            assert_eq!(list.lock().unwrap().as_slice(), b"main.rs\n");
+            assert!(captured.stdout.is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dry_run_with_json_format_writes_json() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("project");
+        fs::create_dir_all(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(input.clone()),
+            format: OutputFormat::Json,
+            dry_run: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+        let (sink, preview) = BundleSink::in_memory();
+        let (result, _) = console::capture::run(|| {
+            execute_with_sink(args, sink, &mut banner::decoration_rng(None))
+        });
+        result?;
+
+        let preview: serde_json::Value = serde_json::from_slice(&preview.lock().unwrap().clone())?;
+        assert_eq!(preview["file_count"], 1);
+        assert_eq!(preview["files"][0]["path"], "main.rs");
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_total_lines_stops_output_across_inputs() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let first = temp_dir.path().join("first");
+        let second = temp_dir.path().join("second");
+        fs::create_dir_all(&first)?;
+        fs::create_dir_all(&second)?;
+        let five_lines = "1\n2\n3\n4\n5\n";
+        fs::write(first.join("a.txt"), five_lines)?;
+        fs::write(second.join("b.txt"), five_lines)?;
+        fs::write(second.join("c.txt"), five_lines)?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![first.clone(), second.clone()],
+            output_path: Some(output.clone()),
+            root: Some(temp_dir.path().to_path_buf()),
+            max_total_lines: Some(10),
+            fast_mode: true,
+            ..Default::default()
+        };
+        execute_with_sink(
+            args,
+            BundleSink::new(io::sink()),
+            &mut banner::decoration_rng(None),
+        )?;
+
+        // a.txt takes 6 lines, the separator 1, and b.txt is cut after 3
+        let content = fs::read_to_string(&output)?;
+        assert_eq!(
+            content,
+            "==> first/a.txt\n1\n2\n3\n4\n5\n\n==> second/b.txt\n1\n2\n\n\
+             ... (truncated by --max-total-lines)\n"
+        );
+        assert!(!content.contains("c.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_total_lines_counts_the_title() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input");
+        fs::create_dir_all(&input)?;
+        fs::write(input.join("a.txt"), "1\n2\n3\n4\n5\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(input.clone()),
+            title: Some("Demo".to_string()),
+            max_total_lines: Some(4),
+            fast_mode: true,
+            ..Default::default()
+        };
+        execute_with_sink(
+            args,
+            BundleSink::new(io::sink()),
+            &mut banner::decoration_rng(None),
+        )?;
+
+        // The title and its date take 2 lines, the separator 1, leaving 1 for a.txt
+        let content = fs::read_to_string(&output)?;
+        let kept = content
+            .strip_suffix("\n... (truncated by --max-total-lines)\n")
+            .unwrap();
+        assert!(kept.starts_with("=== Demo ===\nGenerated: "));
+        assert!(kept.ends_with("\n\n==> a.txt\n"));
+        assert_eq!(kept.lines().count(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_recent_orders_each_input_on_its_own() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(root.join("src"))?;
+        fs::create_dir_all(root.join("web"))?;
+        let now = std::time::SystemTime::now();
+        for (name, age_secs) in [
+            ("src/old.rs", 300),
+            ("src/older.rs", 600),
+            ("web/new.js", 10),
+        ] {
+            let path = root.join(name);
+            fs::write(&path, name)?;
+            fs::File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(now - Duration::from_secs(age_secs))?;
+        }
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![root.join("src"), root.join("web")],
+            output_path: Some(output.clone()),
+            root: Some(root.clone()),
+            recent: true,
+            max_files: Some(2),
+            fast_mode: true,
+            ..Default::default()
+        };
+        execute_with_sink(
+            args,
+            BundleSink::new(io::sink()),
+            &mut banner::decoration_rng(None),
+        )?;
+
+        // The newest file overall sits in the second input, which the shared limit never reaches
+        let content = fs::read_to_string(&output)?;
+        let headers: Vec<_> = content
+            .lines()
+            .filter_map(|line| line.strip_prefix("==> "))
+            .collect();
+        assert_eq!(headers, ["src/old.rs", "src/older.rs"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_covers_every_input_once() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("project");
+        fs::create_dir_all(root.join("src"))?;
+        fs::create_dir_all(root.join("web"))?;
+        fs::write(root.join("src/main.rs"), "fn main() {}\n")?;
+        fs::write(root.join("web/app.js"), "run();\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![root.join("src"), root.join("web")],
+            output_path: Some(output.clone()),
+            root: Some(root.clone()),
+            tree: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+        execute_with_sink(
+            args,
+            BundleSink::new(io::sink()),
+            &mut banner::decoration_rng(None),
+        )?;
+
+        let content = fs::read_to_string(&output)?;
+        let tree = "\
+.
+├── src
+│   └── main.rs
+└── web
+    └── app.js
+";
+        assert!(content.starts_with(&format!("{tree}\n==> src/main.rs\n")));
+        assert_eq!(content.lines().filter(|line| *line == ".").count(), 1);
+        assert!(content.contains("\n==> web/app.js\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rerun_into_scanned_dir_never_bundles_output() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("project");
+        fs::create_dir_all(input.join("src"))?;
+        fs::write(input.join("src/main.rs"), "fn main() {}\n")?;
+
+        // The output lives in the scanned tree, spelled differently from the walk's entries
+        let output = input.join("src").join("..").join(".").join("out.txt");
+        let run_once = || -> anyhow::Result<String> {
+            let args = RunArgs {
+                input_paths: vec![input.clone()],
+                output_path: Some(output.clone()),
+                root: Some(input.clone()),
+                fast_mode: true,
+                ..Default::default()
+            };
+            execute_with_sink(
+                args,
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )?;
+            Ok(fs::read_to_string(input.join("out.txt"))?)
+        };
+
+        let first = run_once()?;
+        let second = run_once()?;
+        assert!(first.contains("==> src/main.rs"));
+        assert!(!second.contains("==> out.txt"));
+        assert_eq!(first, second);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_output_follows_unless_refused() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let target = temp_dir.path().join("target.txt");
+        fs::write(&target, "precious\n")?;
+        let link = temp_dir.path().join("bundle.txt");
+        std::os::unix::fs::symlink(&target, &link)?;
+
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(link.clone()),
+            root: Some(temp_dir.path().to_path_buf()),
+            no_follow_output_symlink: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+        let run = |args| {
+            console::capture::run(|| {
+                execute_with_sink(
+                    args,
+                    BundleSink::new(io::sink()),
+                    &mut banner::decoration_rng(None),
+                )
+            })
+            .0
+        };
+
+        let err = run(args.clone()).unwrap_err();
+        assert!(format!("{err:#}").contains("symlinked output"));
+        assert_eq!(fs::read_to_string(&target)?, "precious\n");
+
+        // By default the link is followed and its target receives the bundle
+        run(RunArgs {
+            no_follow_output_symlink: false,
+            ..args
+        })?;
+        assert!(fs::symlink_metadata(&link)?.file_type().is_symlink());
+        assert!(fs::read_to_string(&target)?.contains("fn main() {}"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_title_is_separated_from_first_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("a.rs"), "fn a() {}\n")?;
+        fs::write(input.join("b.rs"), "fn b() {}\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            title: Some("Two Files".to_string()),
+            stable_timestamps: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        prepare_output(&args, &output, None)?;
+        walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+
+        // The title counts as content, so the first file gets its separator like any other
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "=== Two Files ===\n\n==> src/a.rs\nfn a() {}\n\n==> src/b.rs\nfn b() {}\n"
+        );
+
+        // Split bundles keep the title too, at the top of the first part
+        let args = RunArgs {
+            split_tokens: Some(1_000),
+            ..args
+        };
+        prepare_output(&args, &output, None)?;
+        walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+        assert!(
+            fs::read_to_string(walker::part_path(&output, 1))?
+                .starts_with("=== Two Files ===\n\n==> src/a.rs\n")
+        );
+        Ok(())
+    }
+
+    /// In-memory clipboard used instead of the system one.
+    struct FakeClipboard(String);
+
+    impl clipboard::ClipboardSource for FakeClipboard {
+        fn read_text(&mut self) -> Result<String, crate::core::errors::ClipboardError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    impl clipboard::ClipboardTarget for FakeClipboard {
+        fn write_text(&mut self, text: &str) -> anyhow::Result<()> {
+            self.0 = text.to_string();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stats_to_clipboard_copies_plain_text() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            stats_to_clipboard: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+        let mut tally = prepare_output(&args, &output, None)?;
+        let report =
+            walker::Walker::new(temp_dir.path(), &input, &output, &[]).process_dir(&args)?;
+        tally.merge(report.content);
+
+        let mut clip = FakeClipboard(String::new());
+        write_stats(&mut clip, &bundle_stats(&args, &output, tally)?)?;
+
+        // "==> src/main.rs\nfn main() {}\n" is 29 characters over 3 lines
+        assert!(!clip.0.contains('\x1b'), "ANSI codes in: {:?}", clip.0);
+        assert!(clip.0.starts_with("Content Statistics\n"));
+        assert!(clip.0.contains("Characters: 29\n"));
+        assert!(clip.0.contains("Lines: 3\n"));
+        assert!(clip.0.contains("Size: 29 B\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_input_from_clipboard_bundles_listed_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let first = temp_dir.path().join("first.rs");
+        let second = temp_dir.path().join("second.rs");
+        let unlisted = temp_dir.path().join("unlisted.rs");
+        fs::write(&first, "fn first() {}")?;
+        fs::write(&second, "fn second() {}")?;
+        fs::write(&unlisted, "fn unlisted() {}")?;
+
+        let mut args = RunArgs {
+            root: Some(temp_dir.path().to_path_buf()),
+            stdout: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+        let mut clip = FakeClipboard(format!("{}\r\n\n{}\n", first.display(), second.display()));
+        apply_clipboard_inputs(&mut args, &mut clip)?;
+        assert_eq!(args.input_paths, vec![first.clone(), second.clone()]);
+
+        let (sink, bundle) = BundleSink::in_memory();
+        let (result, _) = console::capture::run(|| {
+            execute_with_sink(args, sink, &mut banner::decoration_rng(None))
+        });
+        result?;
+
+        let bundle = String::from_utf8(bundle.lock().unwrap().clone())?;
+        assert!(bundle.contains("fn first() {}"));
+        assert!(bundle.contains("fn second() {}"));
+        assert!(!bundle.contains("fn unlisted() {}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_input_from_clipboard_rejects_missing_paths() {
+        let mut args = RunArgs::default();
+        let mut clip = FakeClipboard("/nonexistent/file.rs\n".to_string());
+        assert!(apply_clipboard_inputs(&mut args, &mut clip).is_err());
+
+        let mut empty = FakeClipboard("\n  \n".to_string());
+        assert!(apply_clipboard_inputs(&mut args, &mut empty).is_err());
+    }
+
+    #[test]
+    fn test_paths_from_bundles_listed_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let first = temp_dir.path().join("first.rs");
+        let second = temp_dir.path().join("nested/second.rs");
+        fs::create_dir_all(temp_dir.path().join("nested"))?;
+        fs::write(&first, "fn first() {}")?;
+        fs::write(&second, "fn second() {}")?;
+        fs::write(temp_dir.path().join("unlisted.rs"), "fn unlisted() {}")?;
+        let list = temp_dir.path().join("paths.txt");
+        fs::write(
+            &list,
+            format!("{}\n\n{}\n", first.display(), second.display()),
+        )?;
+
+        let args = RunArgs {
+            input_paths: vec![PathBuf::from(".")],
+            paths_from: Some(list),
+            root: Some(temp_dir.path().to_path_buf()),
+            stdout: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+        let (sink, bundle) = BundleSink::in_memory();
+        let (result, _) = console::capture::run(|| {
+            execute_with_sink(args, sink, &mut banner::decoration_rng(None))
+        });
+        result?;
+
+        let bundle = String::from_utf8(bundle.lock().unwrap().clone())?;
+        assert!(bundle.contains("==> first.rs\nfn first() {}"));
+        assert!(bundle.contains("==> nested/second.rs\nfn second() {}"));
+        assert!(!bundle.contains("fn unlisted() {}"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonical_bundles_are_byte_identical() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input");
+        fs::create_dir_all(input.join("src/nested"))?;
+        fs::write(input.join("src/nested/lib.rs"), "pub fn lib() {}\r\n")?;
+        fs::write(input.join("src/main.rs"), "fn main() {}\n")?;
+        fs::write(input.join("README.md"), "# Demo\r\n\r\ntext\r\n")?;
+
+        let bundle = |name: &str| -> anyhow::Result<Vec<u8>> {
+            let output = temp_dir.path().join(name);
+            let args = RunArgs {
+                input_paths: vec![input.clone()],
+                output_path: Some(output.clone()),
+                root: Some(input.clone()),
+                title: Some("Demo".to_string()),
+                canonical: true,
+                fast_mode: true,
+                ..Default::default()
+            };
+            let (result, _) = console::capture::run(|| {
+                execute_with_sink(
+                    args,
+                    BundleSink::new(io::sink()),
+                    &mut banner::decoration_rng(None),
+                )
+            });
+            result?;
+            Ok(fs::read(output)?)
+        };
+
+        let first = bundle("first.txt")?;
+        assert_eq!(first, bundle("second.txt")?);
+        let text = String::from_utf8(first)?;
+        assert!(text.starts_with("=== Demo ===\n"));
+        assert!(!text.contains('\r'));
+        assert!(text.contains("==> src/nested/lib.rs\npub fn lib() {}\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_timeout_aborts_slow_traversal_and_removes_output() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("bundle.txt");
+        fs::write(&output, "==> partial")?;
+
+        // Injected traversal that stalls until released, so only the timeout can end it
+        let (release, stalled) = std::sync::mpsc::channel::<()>();
+        let result = run_traversal_with_timeout(Duration::from_millis(50), &output, move || {
+            let _ = stalled.recv();
+            Ok(exclude::PatternStats::default())
+        });
+        drop(release);
+
+        let error = result.expect_err("slow traversal should time out");
+        assert!(
+            error
+                .downcast_ref::<TraversalError>()
+                .is_some_and(|e| matches!(e, TraversalError::TimedOut(_)))
+        );
+        assert!(!output.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_timeout_lets_fast_traversal_finish() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("bundle.txt");
+
+        let stats = run_traversal_with_timeout(Duration::from_secs(5), &output, || {
+            Ok(exclude::PatternStats::default())
+        })?;
+        assert!(stats.entries().is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_show_stats_with_content() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -304,7 +3085,7 @@ mod run_tests {
         fs::write(&output_path, "Hello\nWorld\nTest content")?;
 
         // This should not panic
-        let result = show_stats(&output_path);
+        let result = show_stats(&output_path, WordMode::Whitespace);
         assert!(result.is_ok());
 
         Ok(())
@@ -0,0 +1,211 @@
+//! stats - Execution logic for the stats command, analyzing a bundle or what one would hold.
+
+use super::args::{RunArgs, StatsArgs, WordMode};
+use super::run;
+use crate::core::errors::FileSystemError;
+use anyhow::Context;
+use std::fs;
+use std::path::Path;
+
+/// Executes the stats command, printing statistics for an existing bundle file or for the
+/// bundle `run` would produce from the given directories.
+///
+/// # Errors
+///
+/// Returns an error if a bundle cannot be read as UTF-8 text, or a traversal fails.
+pub fn execute(args: StatsArgs) -> anyhow::Result<()> {
+    if let [bundle] = args.paths.as_slice()
+        && bundle.is_file()
+    {
+        return bundle_stats(bundle, args.word_mode);
+    }
+
+    let word_mode = args.word_mode;
+    let tally = run::tally_inputs(RunArgs {
+        input_paths: args.paths,
+        exclude: args.exclude,
+        skip_hidden: args.skip_hidden,
+        word_mode,
+        fast_mode: true,
+        ..Default::default()
+    })?;
+    run::show_stats_box(tally.stats(word_mode));
+    Ok(())
+}
+
+/// Prints statistics for an existing bundle file.
+fn bundle_stats(bundle: &Path, word_mode: WordMode) -> anyhow::Result<()> {
+    let content = fs::read_to_string(bundle)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: bundle.to_path_buf(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to read bundle: {}", bundle.display()))?;
+
+    run::show_content_stats(&content, word_mode);
+    Ok(())
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+    use crate::commands::args::WordMode;
+    use crate::core::traversal::sink::BundleSink;
+    use crate::core::ui::formatter::StatsBox;
+    use crate::core::ui::{banner, console};
+    use std::io;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn stats_args(paths: Vec<PathBuf>) -> StatsArgs {
+        StatsArgs {
+            paths,
+            exclude: Vec::new(),
+            skip_hidden: true,
+            word_mode: WordMode::Whitespace,
+        }
+    }
+
+    #[test]
+    fn test_stats_on_known_bundle() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bundle = temp_dir.path().join("bundle.txt");
+        let content = "==> src/main.rs\nfn main() {}\n";
+        fs::write(&bundle, content)?;
+
+        assert_eq!(
+            StatsBox::from_content(content, WordMode::Whitespace),
+            StatsBox::new(3, 29, 5, 8, 29)
+        );
+
+        let (result, captured) = console::capture::run(|| execute(stats_args(vec![bundle])));
+        result?;
+        assert!(captured.stdout.contains("Content Statistics"));
+        assert!(captured.stdout.contains("29 B"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_missing_bundle() {
+        let result = execute(stats_args(vec!["/nonexistent/bundle.txt".into()]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_stats_for_directories_match_a_full_run() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(input.join("target"))?;
+        fs::write(
+            input.join("main.rs"),
+            "fn main() {\n    println!(\"hi\");\n}\n",
+        )?;
+        fs::write(input.join("notes.md"), "# Notes\n\nSome words here.\n")?;
+        fs::write(input.join(".env"), "SECRET=1\n")?;
+        fs::write(input.join("target").join("build.rs"), "// generated\n")?;
+
+        let output = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            exclude: vec!["target".to_string()],
+            skip_hidden: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+
+        let (tally, _) = console::capture::run(|| run::tally_inputs(args.clone()));
+        let (result, _) = console::capture::run(|| {
+            run::execute_with_sink(
+                RunArgs {
+                    output_path: Some(output.clone()),
+                    ..args
+                },
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )
+        });
+        result?;
+
+        let bundle = fs::read_to_string(&output)?;
+        assert!(!bundle.contains("SECRET") && !bundle.contains("generated"));
+        assert_eq!(
+            tally?.stats(WordMode::Whitespace),
+            StatsBox::from_content(&bundle, WordMode::Whitespace)
+        );
+
+        // Traversing writes nothing
+        fs::remove_file(&output)?;
+        let (result, captured) = console::capture::run(|| execute(stats_args(vec![input])));
+        result?;
+        assert!(captured.stdout.contains("Content Statistics"));
+        assert!(!output.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_skip_an_input_without_files() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let empty = temp_dir.path().join("empty");
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(&empty)?;
+        fs::create_dir_all(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+
+        let with_empty = RunArgs {
+            input_paths: vec![empty.clone(), input.clone()],
+            root: Some(temp_dir.path().to_path_buf()),
+            fast_mode: true,
+            ..Default::default()
+        };
+        let alone = RunArgs {
+            input_paths: vec![input],
+            ..with_empty.clone()
+        };
+        let (tally, captured) = console::capture::run(|| run::tally_inputs(with_empty));
+        let (expected, _) = console::capture::run(|| run::tally_inputs(alone));
+
+        assert!(captured.stderr.contains(&empty.display().to_string()));
+        assert_eq!(
+            tally?.stats(WordMode::Whitespace),
+            expected?.stats(WordMode::Whitespace)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_skip_a_leftover_output_file() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("src");
+        fs::create_dir_all(&input)?;
+        fs::write(input.join("main.rs"), "fn main() {}\n")?;
+        let output = input.join("treeclip_temp.txt");
+        fs::write(&output, "==> old bundle\nwith several stale words\n")?;
+
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(output.clone()),
+            root: Some(input.clone()),
+            fast_mode: true,
+            ..Default::default()
+        };
+        let (tally, _) = console::capture::run(|| run::tally_inputs(args.clone()));
+        let (result, _) = console::capture::run(|| {
+            run::execute_with_sink(
+                args,
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )
+        });
+        result?;
+
+        let bundle = fs::read_to_string(&output)?;
+        assert!(!bundle.contains("stale"));
+        assert_eq!(
+            tally?.stats(WordMode::Whitespace),
+            StatsBox::from_content(&bundle, WordMode::Whitespace)
+        );
+        Ok(())
+    }
+}
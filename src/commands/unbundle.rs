@@ -0,0 +1,315 @@
+//! unbundle - Execution logic for the unbundle command, recreating a bundle's files on disk.
+
+use super::args::UnbundleArgs;
+use crate::core::errors::{FileSystemError, UnbundleError};
+use crate::core::ui::console;
+use crate::core::ui::messages::Messages;
+use crate::core::utils;
+use anyhow::Context;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A file section of a bundle: its header path, note, and content lines.
+struct Section<'a> {
+    path: &'a str,
+    note: Option<&'a str>,
+    lines: Vec<&'a str>,
+}
+
+/// Executes the unbundle command, writing every file of the bundle below the output directory.
+///
+/// # Errors
+///
+/// Returns an error if the bundle cannot be read, a header path would escape the output
+/// directory, an encoded section doesn't decode, or a file cannot be written.
+pub fn execute(args: UnbundleArgs) -> anyhow::Result<()> {
+    utils::validate_path_exists(&args.bundle)
+        .with_context(|| format!("Bundle not found: {}", args.bundle.display()))?;
+
+    let content = fs::read_to_string(&args.bundle)
+        .map_err(|e| FileSystemError::ReadFailed {
+            path: args.bundle.clone(),
+            source: e,
+        })
+        .with_context(|| format!("Failed to read bundle: {}", args.bundle.display()))?;
+
+    let count = unbundle(&content, &args.output_dir)?;
+    console::line(Messages::unbundled(
+        count,
+        &args.output_dir.display().to_string(),
+    ));
+    Ok(())
+}
+
+/// Recreates the files of a text bundle below `output_dir`; returns how many were written.
+///
+/// Files bundled without their content (`(binary, ... skipped)`, `(skipped: ...)`) are
+/// reported and left out.
+fn unbundle(bundle: &str, output_dir: &Path) -> anyhow::Result<usize> {
+    let mut count = 0;
+    for section in parse_sections(bundle) {
+        let Some(bytes) = decode_section(&section)? else {
+            console::line(Messages::unbundle_skipped(section.path));
+            continue;
+        };
+
+        let target = output_dir.join(safe_relative_path(section.path)?);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| FileSystemError::CreateDirFailed {
+                    path: parent.to_path_buf(),
+                    source: e,
+                })
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        fs::write(&target, bytes)
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: target.clone(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to write unbundled file: {}", target.display()))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Splits a text bundle into its `==> path` sections.
+///
+/// A header only counts at the start of the bundle or after the blank line separating
+/// sections. Anything before the first header (a `--title` block, a `--tree`) is
+/// ignored, as are `=== *.ext ===` groups from `--merge-ext` and `--dir-headers` lines.
+fn parse_sections(bundle: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut in_section = false;
+    let mut previous_blank = true;
+
+    for line in bundle.lines() {
+        if previous_blank && let Some(header) = line.strip_prefix("==> ") {
+            let (path, note) = split_note(header);
+            sections.push(Section {
+                path,
+                note,
+                lines: Vec::new(),
+            });
+            in_section = true;
+        } else if previous_blank && (line.starts_with("=== ") || is_dir_header(line)) {
+            in_section = false;
+        } else if in_section && let Some(section) = sections.last_mut() {
+            section.lines.push(line);
+        }
+        previous_blank = line.is_empty();
+    }
+
+    // Drop the blank separator lines that ended up at the end of each section
+    for section in &mut sections {
+        while section.lines.last().is_some_and(|line| line.is_empty()) {
+            section.lines.pop();
+        }
+    }
+    sections
+}
+
+/// Checks whether a line is a `--dir-headers` summary, e.g. `## src/ (4 files, 12.0 KB)`.
+fn is_dir_header(line: &str) -> bool {
+    let Some((dir, summary)) = line
+        .strip_prefix("## ")
+        .and_then(|line| line.strip_suffix(')'))
+        .and_then(|line| line.rsplit_once(" ("))
+    else {
+        return false;
+    };
+    let Some((count, size)) = summary.split_once(", ") else {
+        return false;
+    };
+    let counted = [" file", " files"]
+        .iter()
+        .filter_map(|noun| count.strip_suffix(noun))
+        .any(|count| count.parse::<usize>().is_ok());
+    let sized = size
+        .split_once(' ')
+        .is_some_and(|(value, unit)| value.parse::<f64>().is_ok() && unit.ends_with('B'));
+    dir.ends_with('/') && counted && sized
+}
+
+/// Splits a header into its path and the note `run` appends, e.g. `(base64)`.
+///
+/// Only notes `run` writes are recognized, so a file named `a (1).txt` keeps its name.
+/// Annotations from `--per-file-tokens` and `--git-meta` come last and are dropped first.
+fn split_note(header: &str) -> (&str, Option<&str>) {
+    let mut header = header;
+    while let Some((rest, annotation)) = header
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+        && is_annotation(annotation)
+    {
+        header = rest;
+    }
+    header
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+        .filter(|(_, note)| {
+            *note == "base64" || note.starts_with("binary, ") || note.starts_with("skipped: ")
+        })
+        .map_or((header, None), |(path, note)| (path, Some(note)))
+}
+
+/// Checks whether a header note is an annotation: `~<n> tokens` or `last: <author>, <date>`.
+fn is_annotation(note: &str) -> bool {
+    let tokens = note
+        .strip_prefix('~')
+        .and_then(|note| note.strip_suffix(" tokens"));
+    note.starts_with("last: ") || tokens.is_some_and(|count| count.parse::<usize>().is_ok())
+}
+
+/// Returns a section's original bytes, or `None` for a file whose content was skipped
+/// (binary, or over `--max-file-size`).
+fn decode_section(section: &Section) -> anyhow::Result<Option<Vec<u8>>> {
+    let encoding = match section.note {
+        None => {
+            // Text sections were trimmed when bundled; they end in a single newline
+            let mut text = section.lines.join("\n");
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            return Ok(Some(text.into_bytes()));
+        }
+        Some(note) if note.ends_with(" skipped") || note.starts_with("skipped: ") => {
+            return Ok(None);
+        }
+        Some(note) if note.ends_with(" as hex") => "hex",
+        Some(_) => "base64",
+    };
+
+    let encoded = section.lines.concat();
+    let decoded = match encoding {
+        "hex" => decode_hex(&encoded),
+        _ => STANDARD.decode(encoded.as_bytes()).ok(),
+    };
+    decoded.map(Some).ok_or_else(|| {
+        UnbundleError::DecodeFailed {
+            path: section.path.to_string(),
+            encoding,
+        }
+        .into()
+    })
+}
+
+/// Decodes lowercase or uppercase hexadecimal, as written by `--include-binary=hex`.
+fn decode_hex(encoded: &str) -> Option<Vec<u8>> {
+    if !encoded.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..encoded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(encoded.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Checks that a header path stays inside the output directory: relative, without `..`.
+fn safe_relative_path(path: &str) -> Result<PathBuf, UnbundleError> {
+    let relative = Path::new(path);
+    let safe = relative.components().next().is_some()
+        && relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if safe {
+        Ok(relative.to_path_buf())
+    } else {
+        Err(UnbundleError::UnsafePath(path.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod unbundle_tests {
+    use super::*;
+    use crate::commands::args::RunArgs;
+    use crate::commands::run;
+    use crate::core::traversal::sink::BundleSink;
+    use crate::core::ui::banner;
+    use std::io;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_base64_bundle_round_trips_tricky_bytes() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("input");
+        fs::create_dir_all(input.join("nested"))?;
+
+        // NUL bytes, invalid UTF-8, a fake header, CRLF, and trailing whitespace
+        let tricky: &[u8] = b"\0\xff\xfe\n\n==> fake/header.rs\r\n=== *.env ===\n  \t\n";
+        fs::write(input.join("nested/tricky.bin"), tricky)?;
+        fs::write(input.join("plain.txt"), "hello\n\n\n")?;
+        fs::write(input.join("empty.txt"), "")?;
+
+        let bundle = temp_dir.path().join("bundle.txt");
+        let args = RunArgs {
+            input_paths: vec![input.clone()],
+            output_path: Some(bundle.clone()),
+            root: Some(input.clone()),
+            base64: true,
+            fast_mode: true,
+            ..Default::default()
+        };
+        run::execute_with_sink(
+            args,
+            BundleSink::new(io::sink()),
+            &mut banner::decoration_rng(None),
+        )?;
+
+        let content = fs::read_to_string(&bundle)?;
+        assert!(content.contains("==> nested/tricky.bin (base64)\n"));
+        assert!(!content.contains("fake/header"));
+
+        let restored = temp_dir.path().join("restored");
+        assert_eq!(unbundle(&content, &restored)?, 3);
+        assert_eq!(fs::read(restored.join("nested/tricky.bin"))?, tricky);
+        assert_eq!(fs::read(restored.join("plain.txt"))?, b"hello\n\n\n");
+        assert_eq!(fs::read(restored.join("empty.txt"))?, b"");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unbundle_text_sections() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bundle = "=== Title ===\n\n==> src/main.rs (~4 tokens) (last: Ann, 2024-01-02)\nfn main() {}\n\n==> a (1).txt\nx\n\n\
+                      ==> logo.png (binary, 3 B skipped) (last: Ann, 2024-01-02)\n\n==> blob.bin (binary, 2 B as hex)\n00ff\n";
+
+        let (count, _) = console::capture::run(|| unbundle(bundle, temp_dir.path()));
+        assert_eq!(count?, 3);
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("src/main.rs"))?,
+            "fn main() {}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("a (1).txt"))?,
+            "x\n"
+        );
+        assert_eq!(fs::read(temp_dir.path().join("blob.bin"))?, [0x00, 0xff]);
+        assert!(!temp_dir.path().join("logo.png").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unbundle_skips_dir_headers() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bundle =
+            "## ./ (1 file, 2 B)\n\n==> a.txt\na\n\n## src/ (2 files, 1.2 KB)\n\n==> src/b.rs\nb\n";
+
+        assert_eq!(unbundle(bundle, temp_dir.path())?, 2);
+        assert_eq!(fs::read_to_string(temp_dir.path().join("a.txt"))?, "a\n");
+        assert_eq!(fs::read_to_string(temp_dir.path().join("src/b.rs"))?, "b\n");
+        assert!(!is_dir_header("## Usage (see below)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unbundle_rejects_escaping_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        for bundle in ["==> ../evil.txt\nx\n", "==> /etc/evil\nx\n"] {
+            let err = unbundle(bundle, temp_dir.path()).unwrap_err();
+            assert!(err.to_string().contains("outside the output directory"));
+        }
+    }
+}
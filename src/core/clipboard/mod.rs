@@ -1,7 +1,10 @@
 //! clipboard - Handles system clipboard operations for file content.
 
+pub mod osc52;
+
 use crate::core::errors::{ClipboardError, FileSystemError};
 use anyhow::Context;
+use clap::ValueEnum;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -11,12 +14,137 @@ use std::time::Duration;
 /// Maximum clipboard content size (100MB) to prevent memory issues.
 const MAX_CLIPBOARD_SIZE: usize = 100 * 1024 * 1024;
 
+/// How long to hold on after setting the clipboard unless `--clipboard-wait-ms` says otherwise.
+pub const DEFAULT_OWNERSHIP_WAIT: Duration = Duration::from_millis(100);
+
+/// Mechanism used to place content on the clipboard.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClipboardBackend {
+    /// The operating system clipboard (X11/Wayland/macOS/Windows)
+    #[default]
+    System,
+    /// The terminal's clipboard via the OSC 52 escape sequence (works over SSH)
+    Osc52,
+}
+
+/// Representations a bundle is placed on the system clipboard in.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClipboardFormat {
+    /// Plain text only
+    #[default]
+    Text,
+    /// HTML (rendered from a Markdown bundle) with a plain-text fallback
+    Html,
+}
+
+/// Clipboard content formats that can actually be placed on the system clipboard.
+///
+/// arboard only exposes plain text and HTML targets on every platform, so any other
+/// MIME hint is resolved to `PlainText` by the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardMime {
+    /// `text/plain` - supported everywhere.
+    PlainText,
+    /// `text/html` - the content is wrapped in an escaped `<pre>` block,
+    /// with the raw text registered as the plain-text alternative.
+    Html,
+    /// `text/html` rendered from Markdown content (headings, code blocks),
+    /// with the raw Markdown registered as the plain-text alternative.
+    RenderedMarkdown,
+}
+
+impl ClipboardMime {
+    /// Resolves a user-supplied MIME hint into a supported clipboard format.
+    ///
+    /// Returns `None` when the platform clipboard cannot honor the hint
+    /// (e.g. `text/markdown`), in which case callers should fall back to plain text.
+    pub fn from_hint(hint: &str) -> Option<Self> {
+        let essence = hint.split(';').next().unwrap_or_default().trim();
+        match essence.to_ascii_lowercase().as_str() {
+            "text/plain" | "text" | "plain" => Some(Self::PlainText),
+            "text/html" | "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+
+/// Something clipboard text can be read from.
+///
+/// Abstracts the system clipboard so features that consume clipboard input
+/// can be exercised without a display server.
+pub trait ClipboardSource {
+    /// Returns the current clipboard content as text.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClipboardError::GetFailed` if the clipboard holds no text or can't be read.
+    fn read_text(&mut self) -> Result<String, ClipboardError>;
+}
+
+impl ClipboardSource for arboard::Clipboard {
+    fn read_text(&mut self) -> Result<String, ClipboardError> {
+        self.get_text()
+            .map_err(|e| ClipboardError::GetFailed(format!("Clipboard read failed: {}", e)))
+    }
+}
+
+/// Something clipboard text can be written to.
+///
+/// The writing counterpart of [`ClipboardSource`].
+pub trait ClipboardTarget {
+    /// Replaces the clipboard content with `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClipboardError::SetFailed` if the clipboard can't be written.
+    fn write_text(&mut self, text: &str) -> anyhow::Result<()>;
+}
+
+/// The system clipboard as a [`ClipboardTarget`], holding on after each write like
+/// [`Clipboard`] does.
+struct SystemTarget {
+    clip: arboard::Clipboard,
+    /// How long to hold on after setting the clipboard, so it keeps the content.
+    wait: Duration,
+}
+
+impl ClipboardTarget for SystemTarget {
+    fn write_text(&mut self, text: &str) -> anyhow::Result<()> {
+        finish_set(self.clip.set().text(text), self.wait)
+    }
+}
+
+/// Opens the system clipboard for reading.
+///
+/// # Errors
+///
+/// Returns `ClipboardError::InitializationFailed` if the clipboard cannot be initialized.
+pub fn system_source() -> Result<impl ClipboardSource, ClipboardError> {
+    arboard::Clipboard::new().map_err(|e| {
+        ClipboardError::InitializationFailed(format!("Failed to access system clipboard: {}", e))
+    })
+}
+
+/// Opens the system clipboard for writing, holding on for `wait` after each write.
+///
+/// # Errors
+///
+/// Returns `ClipboardError::InitializationFailed` if the clipboard cannot be initialized.
+pub fn system_target(wait: Duration) -> Result<impl ClipboardTarget, ClipboardError> {
+    let clip = arboard::Clipboard::new().map_err(|e| {
+        ClipboardError::InitializationFailed(format!("Failed to access system clipboard: {}", e))
+    })?;
+    Ok(SystemTarget { clip, wait })
+}
+
 /// Clipboard provides an interface to interact with the system clipboard.
 pub struct Clipboard {
     /// Path to the data file to be copied to clipboard.
     data: PathBuf,
     /// Handle to the system clipboard.
     clip: arboard::Clipboard,
+    /// How long to hold on after setting the clipboard, so it keeps the content.
+    wait: Duration,
 }
 
 impl Clipboard {
@@ -36,10 +164,31 @@ impl Clipboard {
         Ok(Self {
             data: data.to_path_buf(),
             clip,
+            wait: DEFAULT_OWNERSHIP_WAIT,
         })
     }
 
-    /// Reads the output file and places its contents into the system clipboard.
+    /// Sets how long to hold on after setting the clipboard; zero returns right away.
+    pub fn with_wait(mut self, wait: Duration) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Reads the output file and places its contents into the system clipboard as plain text.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Clipboard::set_clipboard_as`].
+    #[cfg(test)]
+    pub fn set_clipboard(&mut self) -> anyhow::Result<()> {
+        self.set_clipboard_as(ClipboardMime::PlainText)
+    }
+
+    /// Reads the output file and places its contents into the system clipboard
+    /// using the requested format.
+    ///
+    /// For the HTML formats the raw text is also registered as the plain-text
+    /// alternative, so applications without HTML support still receive the content.
     ///
     /// # Platform Notes
     ///
@@ -57,7 +206,7 @@ impl Clipboard {
     /// - File cannot be read
     /// - File is too large (>100MB)
     /// - Clipboard cannot be accessed
-    pub fn set_clipboard(&mut self) -> anyhow::Result<()> {
+    pub fn set_clipboard_as(&mut self, mime: ClipboardMime) -> anyhow::Result<()> {
         // Check file size first
         let metadata = std::fs::metadata(&self.data)
             .with_context(|| format!("Failed to read file metadata: {}", self.data.display()))?;
@@ -71,8 +220,8 @@ impl Clipboard {
             .into());
         }
 
-        // TODO: Optimize for huge files - consider streaming or chunking instead of loading entire file
-        // Read entire file into memory (clipboard APIs require full content as string)
+        // Read entire file into memory: clipboard APIs require the full content as one string,
+        // and MAX_CLIPBOARD_SIZE above bounds how large that can get
         let mut output_file = File::options()
             .read(true)
             .open(&self.data)
@@ -101,18 +250,132 @@ impl Clipboard {
                 )
             })?;
 
-        // Set clipboard text
+        // Set clipboard content
         // On Linux, clipboard managers usually take ownership immediately
-        self.clip
-            .set()
-            .text(output_content)
-            .map_err(|e| ClipboardError::SetFailed(format!("Clipboard operation failed: {}", e)))
-            .with_context(|| "Failed to set clipboard content - clipboard may not be available")?;
+        let html = match mime {
+            ClipboardMime::PlainText => None,
+            ClipboardMime::Html => Some(wrap_as_html(&output_content)),
+            ClipboardMime::RenderedMarkdown => Some(markdown_to_html(&output_content)),
+        };
+        match html {
+            Some(html) => self.set_clipboard_html(html, output_content),
+            None => finish_set(self.clip.set().text(output_content), self.wait),
+        }
+    }
+
+    /// Places `html` on the system clipboard, with `alt_text` as the plain-text alternative
+    /// for applications that can't paste HTML.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClipboardError::SetFailed` if the clipboard can't be written.
+    pub fn set_clipboard_html(&mut self, html: String, alt_text: String) -> anyhow::Result<()> {
+        finish_set(self.clip.set().html(html, Some(alt_text)), self.wait)
+    }
+}
 
-        // NOTE: Sleep guarantees clipboard ownership (required by arboard on some platforms)
-        thread::sleep(Duration::from_millis(100));
+// -------------------------------------------- Private Helper Functions --------------------------------------------
 
-        Ok(())
+/// Reports the outcome of setting the clipboard, then holds on for `wait` to keep it.
+fn finish_set(result: Result<(), arboard::Error>, wait: Duration) -> anyhow::Result<()> {
+    result
+        .map_err(|e| ClipboardError::SetFailed(format!("Clipboard operation failed: {}", e)))
+        .with_context(|| "Failed to set clipboard content - clipboard may not be available")?;
+
+    // NOTE: Sleep guarantees clipboard ownership (required by arboard on some platforms);
+    // pipelines that don't paste right away can skip it with --clipboard-wait-ms 0
+    if !wait.is_zero() {
+        thread::sleep(wait);
+    }
+
+    Ok(())
+}
+
+/// Wraps plain text in a `<pre>` block, escaping HTML special characters.
+fn wrap_as_html(content: &str) -> String {
+    let mut html = String::with_capacity(content.len() + 16);
+    html.push_str("<pre>");
+    push_escaped(&mut html, content);
+    html.push_str("</pre>");
+    html
+}
+
+/// Renders the Markdown `--format markdown` writes as HTML.
+///
+/// Understands ATX headings, fenced code blocks (with their language), and inline
+/// code spans; any other non-blank line becomes a paragraph of escaped text.
+fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::with_capacity(markdown.len() * 2);
+    // The backticks that opened the current code block, if inside one
+    let mut open_fence: Option<&str> = None;
+
+    for line in markdown.lines() {
+        if let Some(fence) = open_fence {
+            if line == fence {
+                html.push_str("</code></pre>\n");
+                open_fence = None;
+            } else {
+                push_escaped(&mut html, line);
+                html.push('\n');
+            }
+            continue;
+        }
+
+        let ticks = line.len() - line.trim_start_matches('`').len();
+        let level = line.len() - line.trim_start_matches('#').len();
+        if ticks >= 3 {
+            let (fence, language) = line.split_at(ticks);
+            html.push_str("<pre><code");
+            if !language.trim().is_empty() {
+                html.push_str(" class=\"language-");
+                push_escaped(&mut html, language.trim());
+                html.push('"');
+            }
+            html.push('>');
+            open_fence = Some(fence);
+        } else if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+            html.push_str(&format!("<h{level}>"));
+            push_inline(&mut html, line[level..].trim());
+            html.push_str(&format!("</h{level}>\n"));
+        } else if !line.trim().is_empty() {
+            html.push_str("<p>");
+            push_inline(&mut html, line);
+            html.push_str("</p>\n");
+        }
+    }
+
+    // An unterminated block runs to the end of the document
+    if open_fence.is_some() {
+        html.push_str("</code></pre>\n");
+    }
+    html
+}
+
+/// Appends Markdown text with its `` `code` `` spans rendered as `<code>`.
+fn push_inline(html: &mut String, text: &str) {
+    for (index, part) in text.split('`').enumerate() {
+        // Odd parts sit between a pair of backticks
+        let code = index % 2 == 1;
+        if code {
+            html.push_str("<code>");
+        }
+        push_escaped(html, part);
+        if code {
+            html.push_str("</code>");
+        }
+    }
+}
+
+/// Appends `text` with HTML special characters escaped.
+fn push_escaped(html: &mut String, text: &str) {
+    for char in text.chars() {
+        match char {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            _ => html.push(char),
+        }
     }
 }
 
@@ -120,8 +383,21 @@ impl Clipboard {
 mod clipboard_tests {
     use super::*;
     use std::fs;
+    use std::time::Instant;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_zero_wait_skips_ownership_sleep() -> anyhow::Result<()> {
+        let start = Instant::now();
+        finish_set(Ok(()), Duration::ZERO)?;
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        let start = Instant::now();
+        finish_set(Ok(()), DEFAULT_OWNERSHIP_WAIT)?;
+        assert!(start.elapsed() >= DEFAULT_OWNERSHIP_WAIT);
+        Ok(())
+    }
+
     #[test]
     fn test_clipboard_creation() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -202,6 +478,80 @@ mod clipboard_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_clipboard_mime_from_hint() {
+        assert_eq!(
+            ClipboardMime::from_hint("text/plain"),
+            Some(ClipboardMime::PlainText)
+        );
+        assert_eq!(
+            ClipboardMime::from_hint("TEXT/HTML; charset=utf-8"),
+            Some(ClipboardMime::Html)
+        );
+        // Not representable through the platform clipboard APIs
+        assert_eq!(ClipboardMime::from_hint("text/markdown"), None);
+    }
+
+    #[test]
+    fn test_wrap_as_html_escapes_content() {
+        let html = wrap_as_html("if a < b && c > d { \"x\" }");
+        assert_eq!(
+            html,
+            "<pre>if a &lt; b &amp;&amp; c &gt; d { &quot;x&quot; }</pre>"
+        );
+    }
+
+    #[test]
+    fn test_set_clipboard_as_html() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("test.txt");
+        fs::write(&file_path, "<b>bold</b>")?;
+
+        // May fail in CI environments without clipboard support
+        let Ok(mut clipboard) = Clipboard::new(&file_path) else {
+            return Ok(());
+        };
+
+        if let Err(e) = clipboard.set_clipboard_as(ClipboardMime::Html) {
+            let error_msg = format!("{:?}", e);
+            assert!(error_msg.contains("clipboard") || error_msg.contains("Failed to"));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_markdown_to_html_renders_bundle() {
+        let markdown = "## `src/a<b>.rs`\n\n```rust\nfn lt() -> bool { 1 < 2 }\n```\n\n\
+                        ## `notes.md`\n\n````markdown\n```\ninner\n```\n````\n";
+        assert_eq!(
+            markdown_to_html(markdown),
+            "<h2><code>src/a&lt;b&gt;.rs</code></h2>\n\
+             <pre><code class=\"language-rust\">fn lt() -&gt; bool { 1 &lt; 2 }\n</code></pre>\n\
+             <h2><code>notes.md</code></h2>\n\
+             <pre><code class=\"language-markdown\">```\ninner\n```\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn test_set_clipboard_html() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("bundle.md");
+        fs::write(&file_path, "## `main.rs`\n\n```rust\nfn main() {}\n```\n")?;
+
+        // May fail in CI environments without clipboard support
+        let Ok(mut clipboard) = Clipboard::new(&file_path) else {
+            return Ok(());
+        };
+
+        if let Err(e) = clipboard.set_clipboard_as(ClipboardMime::RenderedMarkdown) {
+            let error_msg = format!("{:?}", e);
+            assert!(error_msg.contains("clipboard") || error_msg.contains("Failed to"));
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_clipboard_nonexistent_file_error() {
         let file_path = PathBuf::from("/nonexistent/file.txt");
@@ -0,0 +1,112 @@
+//! osc52 - Copies text through the terminal with the OSC 52 escape sequence.
+//!
+//! OSC 52 asks the terminal emulator itself to set the clipboard, which works over
+//! SSH and inside containers where no system clipboard is reachable. The payload is
+//! base64-encoded inside `ESC ] 52 ; c ; <payload> BEL`.
+//!
+//! Many terminals cap the size of an escape sequence, so large bundles can be
+//! gzip-compressed first. The clipboard then receives [`COMPRESSED_MARKER`]
+//! followed by the base64 of the gzip stream, and the receiving side must inflate
+//! it (strip the marker, then `base64 -d | gunzip`) - a plain paste shows the encoded text.
+
+use crate::core::ui::console;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::io::{self, Write};
+
+/// Prefix marking clipboard text as a base64-encoded gzip stream.
+pub const COMPRESSED_MARKER: &str = "treeclip+gzip:";
+
+/// Copies `content` to the clipboard by emitting an OSC 52 sequence to the terminal.
+///
+/// # Errors
+///
+/// Returns an error if compression fails.
+pub fn copy(content: &str, compress: bool) -> io::Result<()> {
+    console::inline(sequence(content, compress)?);
+    Ok(())
+}
+
+/// Builds the complete OSC 52 escape sequence for `content`.
+///
+/// # Errors
+///
+/// Returns an error if compression fails.
+pub fn sequence(content: &str, compress: bool) -> io::Result<String> {
+    let clipboard_text = if compress {
+        format!("{COMPRESSED_MARKER}{}", STANDARD.encode(gzip(content)?))
+    } else {
+        content.to_string()
+    };
+    Ok(format!("\x1b]52;c;{}\x07", STANDARD.encode(clipboard_text)))
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Gzip-compresses text at the default compression level.
+fn gzip(content: &str) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content.as_bytes())?;
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod osc52_tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    /// Restores the original text from clipboard content produced by [`copy`]; text
+    /// without the [`COMPRESSED_MARKER`] is returned unchanged.
+    fn decode_payload(clipboard_text: &str) -> io::Result<String> {
+        let Some(encoded) = clipboard_text.strip_prefix(COMPRESSED_MARKER) else {
+            return Ok(clipboard_text.to_string());
+        };
+
+        let compressed = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut content = String::new();
+        GzDecoder::new(compressed.as_slice()).read_to_string(&mut content)?;
+        Ok(content)
+    }
+
+    /// Extracts and base64-decodes what the terminal would put on the clipboard.
+    fn clipboard_text_from(sequence: &str) -> String {
+        let payload = sequence
+            .strip_prefix("\x1b]52;c;")
+            .and_then(|rest| rest.strip_suffix('\x07'))
+            .expect("well-formed OSC 52 sequence");
+        String::from_utf8(STANDARD.decode(payload).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_plain_sequence_round_trips() -> io::Result<()> {
+        let content = "==> src/main.rs\nfn main() {}\n";
+        let clipboard_text = clipboard_text_from(&sequence(content, false)?);
+        assert_eq!(clipboard_text, content);
+        assert_eq!(decode_payload(&clipboard_text)?, content);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compressed_sequence_inflates_to_original() -> io::Result<()> {
+        let content = "==> src/lib.rs\npub fn answer() -> u32 { 42 }\n".repeat(200);
+
+        let compressed = sequence(&content, true)?;
+        let clipboard_text = clipboard_text_from(&compressed);
+        assert!(clipboard_text.starts_with(COMPRESSED_MARKER));
+        assert_eq!(decode_payload(&clipboard_text)?, content);
+
+        // Repetitive bundles shrink considerably
+        assert!(compressed.len() < sequence(&content, false)?.len() / 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_rejects_corrupt_payload() {
+        assert!(decode_payload(&format!("{COMPRESSED_MARKER}not-base64!")).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+//! config - Loads persistent `treeclip run` defaults from a `treeclip.toml` file.
+//!
+//! The first file found wins: `treeclip.toml` in the root directory, then
+//! `$XDG_CONFIG_HOME/treeclip/treeclip.toml` (`~/.config` if unset). Its values sit
+//! under the command line: a flag given there always takes precedence, and pattern
+//! lists are prepended so CLI patterns come last.
+//!
+//! ```toml
+//! exclude = ["target", "*.lock"]
+//! skip_hidden = true
+//! format = "markdown"
+//! max_file_size = "1MB"
+//! ```
+
+use crate::commands::args::{self, OutputFormat, RunArgs, SortOrder};
+use crate::core::errors::{ConfigError, FileSystemError};
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// Name of the config file.
+pub const CONFIG_FILE: &str = "treeclip.toml";
+
+/// Defaults for `treeclip run`, as read from a config file.
+///
+/// Keys are named after the `RunArgs` fields they set; unknown keys are rejected so
+/// a typo doesn't silently do nothing.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    exclude: Vec<String>,
+    include: Vec<String>,
+    skip_hidden: Option<bool>,
+    format: Option<String>,
+    sort: Option<String>,
+    max_file_size: Option<SizeValue>,
+    max_depth: Option<usize>,
+    tree: Option<bool>,
+    stats: Option<bool>,
+    clipboard: Option<bool>,
+    fast_mode: Option<bool>,
+}
+
+/// Flags that need another flag a config can also turn on, as `(flag, required flag,
+/// whether the merged args have it on)`. clap checks its requirements before the config
+/// is read, so these are checked by [`check_requires`] instead.
+const CONFIG_REQUIRES: &[(&str, &str, Switch)] = &[
+    ("tree-show-empty-dirs", "tree", |args| args.tree),
+    ("clipboard-mime", "clipboard", |args| args.clipboard),
+    ("clipboard-format", "clipboard", |args| args.clipboard),
+    (
+        "clipboard-wait-ms",
+        "clipboard or --stats-to-clipboard",
+        |args| args.clipboard || args.stats_to_clipboard,
+    ),
+    ("compress-clipboard", "clipboard", |args| args.clipboard),
+    ("stats-by-ext", "stats", |args| args.stats),
+];
+
+/// Flags that can't be combined, where a config can turn either one on, as `(flag,
+/// other flag, whether the merged args have both on)`. clap only sees the command line,
+/// so these are checked by [`check_conflicts`] once the config is merged. A `format` or
+/// `sort` counts as on when it isn't the default, as only then does it change the run.
+#[rustfmt::skip]
+const CONFIG_CONFLICTS: &[(&str, &str, Switch)] = &[
+    ("split-by-dir", "clipboard", |args| args.split_by_dir && args.clipboard),
+    ("split-by-dir", "stats", |args| args.split_by_dir && args.stats),
+    ("split-by-dir", "format", |args| args.split_by_dir && non_default_format(args)),
+    ("split-tokens", "clipboard", |args| args.split_tokens.is_some() && args.clipboard),
+    ("split-tokens", "stats", |args| args.split_tokens.is_some() && args.stats),
+    ("split-tokens", "format", |args| args.split_tokens.is_some() && non_default_format(args)),
+    ("stats-to-clipboard", "clipboard", |args| args.stats_to_clipboard && args.clipboard),
+    ("recent", "sort", |args| args.recent && non_default_sort(args)),
+    ("canonical", "sort", |args| args.canonical && non_default_sort(args)),
+];
+
+/// Reads whether a flag is on in the merged args.
+type Switch = fn(&RunArgs) -> bool;
+
+/// A size given as a number of bytes or as text like `"10MB"`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SizeValue {
+    Bytes(u64),
+    Text(String),
+}
+
+impl Config {
+    /// Finds and loads the config file for a run rooted at `root`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config file exists but cannot be read or parsed.
+    pub fn discover(root: &Path) -> anyhow::Result<Option<(PathBuf, Config)>> {
+        let Some(path) = candidates(root).into_iter().find(|path| path.is_file()) else {
+            return Ok(None);
+        };
+        let config = Self::load(&path)?;
+        Ok(Some((path, config)))
+    }
+
+    /// Loads the config file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or isn't a valid config.
+    pub fn load(path: &Path) -> anyhow::Result<Config> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| FileSystemError::ReadFailed {
+                path: path.to_path_buf(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        toml::from_str(&content)
+            .map_err(|e| ConfigError::Malformed {
+                path: path.to_path_buf(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    }
+
+    /// Fills in `args` from the config wherever `set_on_cli` reports that the
+    /// command line left the `RunArgs` field (by name) alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidValue` if a value can't be used, e.g. an unknown format.
+    pub fn apply(
+        self,
+        args: &mut RunArgs,
+        set_on_cli: impl Fn(&str) -> bool,
+    ) -> anyhow::Result<()> {
+        // Patterns accumulate; CLI patterns come last so they can override the config's
+        args.exclude.splice(0..0, self.exclude);
+        args.include.splice(0..0, self.include);
+
+        let format = self
+            .format
+            .map(|value| parse_value::<OutputFormat>("format", value))
+            .transpose()?;
+        let sort = self
+            .sort
+            .map(|value| parse_value::<SortOrder>("sort", value))
+            .transpose()?;
+        let max_file_size = self
+            .max_file_size
+            .map(|value| match value {
+                SizeValue::Bytes(bytes) => Ok(bytes),
+                SizeValue::Text(text) => {
+                    args::parse_size(&text).map_err(|reason| ConfigError::InvalidValue {
+                        key: "max_file_size",
+                        value: text,
+                        reason,
+                    })
+                }
+            })
+            .transpose()?;
+        let max_depth = self
+            .max_depth
+            .map(|depth| {
+                args::validate_positive(&depth.to_string()).map_err(|reason| {
+                    ConfigError::InvalidValue {
+                        key: "max_depth",
+                        value: depth.to_string(),
+                        reason,
+                    }
+                })
+            })
+            .transpose()?;
+
+        let unset = |field: &str| !set_on_cli(field);
+        fill(
+            &mut args.skip_hidden,
+            self.skip_hidden,
+            unset("skip_hidden"),
+        );
+        fill(&mut args.format, format, unset("format"));
+        fill(&mut args.sort, sort, unset("sort"));
+        fill(
+            &mut args.max_file_size,
+            max_file_size.map(Some),
+            unset("max_file_size"),
+        );
+        fill(&mut args.max_depth, max_depth.map(Some), unset("max_depth"));
+        fill(&mut args.tree, self.tree, unset("tree"));
+        fill(&mut args.stats, self.stats, unset("stats"));
+        fill(&mut args.clipboard, self.clipboard, unset("clipboard"));
+        fill(&mut args.fast_mode, self.fast_mode, unset("fast_mode"));
+        Ok(())
+    }
+}
+
+/// Checks the flags that need `--tree`, `--clipboard`, or `--stats` once the config is
+/// merged into `args`, so a config turning those on satisfies them too.
+///
+/// `set_on_cli` reports whether the command line gave a `RunArgs` field (by name).
+///
+/// # Errors
+///
+/// Returns `ConfigError::MissingRequirement` for the first flag whose requirement is off.
+pub fn check_requires(args: &RunArgs, set_on_cli: impl Fn(&str) -> bool) -> anyhow::Result<()> {
+    for &(flag, required, is_on) in CONFIG_REQUIRES {
+        if set_on_cli(&flag.replace('-', "_")) && !is_on(args) {
+            return Err(ConfigError::MissingRequirement { flag, required }.into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks the flags that clap keeps apart once the config is merged into `args`, so a
+/// config value can't sneak in a combination the command line would reject.
+///
+/// # Errors
+///
+/// Returns `ConfigError::Conflict` for the first pair of flags that are both on.
+pub fn check_conflicts(args: &RunArgs) -> anyhow::Result<()> {
+    for &(flag, other, both_on) in CONFIG_CONFLICTS {
+        if both_on(args) {
+            return Err(ConfigError::Conflict { flag, other }.into());
+        }
+    }
+    Ok(())
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Whether `--format` picks something other than the default text bundle.
+fn non_default_format(args: &RunArgs) -> bool {
+    args.format != OutputFormat::default()
+}
+
+/// Whether `--sort` picks something other than the default path order.
+fn non_default_sort(args: &RunArgs) -> bool {
+    args.sort != SortOrder::default()
+}
+
+/// Where a config file is looked for, in order.
+fn candidates(root: &Path) -> Vec<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+    let mut candidates = vec![root.join(CONFIG_FILE)];
+    candidates.extend(config_home.map(|dir| dir.join("treeclip").join(CONFIG_FILE)));
+    candidates
+}
+
+/// Overwrites `field` with the config's `value`, if there is one and `unset` allows it.
+fn fill<T>(field: &mut T, value: Option<T>, unset: bool) {
+    if let Some(value) = value
+        && unset
+    {
+        *field = value;
+    }
+}
+
+/// Parses a config value the way clap parses the matching flag's value.
+fn parse_value<T: ValueEnum>(key: &'static str, value: String) -> Result<T, ConfigError> {
+    T::from_str(&value, true).map_err(|reason| ConfigError::InvalidValue { key, value, reason })
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    use crate::commands::run;
+    use crate::core::traversal::sink::BundleSink;
+    use crate::core::ui::{banner, console};
+    use std::io;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_sits_under_cli() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(project.join("src"))?;
+        fs::create_dir_all(project.join("target"))?;
+        fs::write(project.join("src/main.rs"), "fn main() {}\n")?;
+        fs::write(project.join("target/build.rs"), "// generated\n")?;
+        fs::write(project.join("notes.txt"), "notes\n")?;
+        fs::write(
+            project.join(CONFIG_FILE),
+            "exclude = [\"target\"]\nformat = \"markdown\"\nsort = \"size\"\nmax_file_size = \"1MB\"\n",
+        )?;
+
+        // The command line asked for --include '*.rs' and --sort path
+        let output = temp_dir.path().join("bundle.md");
+        let mut args = RunArgs {
+            input_paths: vec![project.clone()],
+            output_path: Some(output.clone()),
+            root: Some(project.clone()),
+            include: vec!["*.rs".to_string()],
+            fast_mode: true,
+            ..Default::default()
+        };
+        let (path, config) = Config::discover(&project)?.expect("config in the root");
+        assert_eq!(path, project.join(CONFIG_FILE));
+        config.apply(&mut args, |field| field == "include" || field == "sort")?;
+
+        assert_eq!(args.format, OutputFormat::Markdown);
+        assert_eq!(args.sort, SortOrder::Path);
+        assert_eq!(args.max_file_size, Some(1024 * 1024));
+
+        console::capture::run(|| {
+            run::execute_with_sink(
+                args,
+                BundleSink::new(io::sink()),
+                &mut banner::decoration_rng(None),
+            )
+        })
+        .0?;
+        let bundle = fs::read_to_string(&output)?;
+        assert!(bundle.contains("## `src/main.rs`"));
+        assert!(!bundle.contains("build.rs"));
+        assert!(!bundle.contains("notes.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed_config_is_a_clear_error() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join(CONFIG_FILE);
+
+        fs::write(&path, "exclude = [\"target\"\n")?;
+        let err = format!("{:#}", Config::load(&path).unwrap_err());
+        assert!(err.contains("Failed to parse config file"));
+        assert!(err.contains(&path.display().to_string()));
+
+        fs::write(&path, "exlude = [\"target\"]\n")?;
+        assert!(Config::load(&path).is_err());
+
+        fs::write(&path, "format = \"yaml\"\n")?;
+        let err = Config::load(&path)?
+            .apply(&mut RunArgs::default(), |_| false)
+            .unwrap_err();
+        assert!(err.to_string().contains("format"));
+
+        // Values go through the same validation as the flags
+        fs::write(&path, "max_depth = 0\n")?;
+        let err = Config::load(&path)?
+            .apply(&mut RunArgs::default(), |_| false)
+            .unwrap_err();
+        assert!(err.to_string().contains("max_depth"));
+        assert!(err.to_string().contains("greater than zero"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_satisfies_requirements() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join(CONFIG_FILE);
+        let given = |field: &str| field == "clipboard_wait_ms";
+
+        // --clipboard-wait-ms alone needs --clipboard from somewhere
+        let mut args = RunArgs {
+            clipboard_wait_ms: Some(0),
+            ..Default::default()
+        };
+        let err = check_requires(&args, given).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("--clipboard-wait-ms requires --clipboard")
+        );
+
+        // --stats-to-clipboard holds the clipboard the same way
+        check_requires(
+            &RunArgs {
+                stats_to_clipboard: true,
+                ..args.clone()
+            },
+            given,
+        )?;
+
+        fs::write(&path, "clipboard = true\n")?;
+        Config::load(&path)?.apply(&mut args, given)?;
+        assert!(args.clipboard);
+        check_requires(&args, given)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_values_respect_conflicts() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join(CONFIG_FILE);
+        let given = |field: &str| field == "split_by_dir" || field == "canonical";
+
+        // `--split-by-dir` on the command line, `stats = true` in the config
+        let mut args = RunArgs {
+            split_by_dir: true,
+            ..Default::default()
+        };
+        check_conflicts(&args)?;
+        fs::write(&path, "stats = true\n")?;
+        Config::load(&path)?.apply(&mut args, given)?;
+        let err = check_conflicts(&args).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("--split-by-dir can't be combined with --stats")
+        );
+
+        // `--canonical` with a config sort order; the default order changes nothing
+        let mut args = RunArgs {
+            canonical: true,
+            ..Default::default()
+        };
+        fs::write(&path, "sort = \"path\"\n")?;
+        Config::load(&path)?.apply(&mut args, given)?;
+        check_conflicts(&args)?;
+        fs::write(&path, "sort = \"size\"\n")?;
+        Config::load(&path)?.apply(&mut args, given)?;
+        let err = check_conflicts(&args).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("--canonical can't be combined with --sort")
+        );
+        Ok(())
+    }
+}
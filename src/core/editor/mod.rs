@@ -5,13 +5,23 @@
 //! Retrieved 2025-12-16, License - CC BY-SA 4.0
 
 use crate::core::errors::{EditorError, FileSystemError};
+use crate::core::ui::console;
+use crate::core::utils;
 use anyhow::Context;
-use std::path::Path;
-use std::{env, fs, process};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io, process};
 
-/// Opens the file in the system's default text editor.
+/// CLI editors tried, in order, when the default editor fails (`--fallback-editors`).
 ///
-/// Falls back to nano if the default editor is not found.
+/// `$NAME` entries are read from the environment and skipped when unset.
+pub const DEFAULT_FALLBACK_EDITORS: &[&str] = &["$VISUAL", "$EDITOR", "nano", "vi"];
+
+/// Opens the file with `editor_cmd` if given, otherwise in the system's default text editor.
+///
+/// A custom command (`--editor-cmd`, e.g. `code --wait`) is run directly and waited for;
+/// its failure is final. The default editor falls back to the first CLI editor of
+/// `fallback_editors` that can be launched (or of [`DEFAULT_FALLBACK_EDITORS`] if it's empty).
 ///
 /// # Platform-specific behavior
 ///
@@ -19,13 +29,28 @@ use std::{env, fs, process};
 /// - **macOS**: Uses `open` command
 /// - **Unix/Linux**: Uses `xdg-open` command
 ///
-/// If the graphical editor fails, attempts to use the CLI editor specified
-/// in the `EDITOR` environment variable, or `/bin/nano` as final fallback.
+/// If the graphical editor fails, each fallback is looked up on `PATH` and the
+/// first one found is launched.
 ///
 /// # Errors
 ///
-/// Returns `EditorError` if neither the default editor nor the fallback editor can be executed.
-pub fn open(path: &Path) -> anyhow::Result<()> {
+/// Returns `EditorError` if the custom command fails, or neither the default editor nor any
+/// fallback editor can be executed.
+pub fn open(
+    path: &Path,
+    editor_cmd: Option<&str>,
+    fallback_editors: &[String],
+) -> anyhow::Result<()> {
+    if let Some(editor_cmd) = editor_cmd {
+        let canonical_path = utils::canonicalize_path(path)?;
+        return open_with_cli_editor(
+            &canonical_path,
+            &[editor_cmd.to_string()],
+            &mut SystemRunner,
+        )
+        .with_context(|| format!("Editor command '{editor_cmd}' failed"));
+    }
+
     let command = get_platform_open_command();
 
     if command.is_empty() {
@@ -35,31 +60,21 @@ pub fn open(path: &Path) -> anyhow::Result<()> {
         .into());
     }
 
-    let canonical_path = path
-        .canonicalize()
-        .map_err(|e| FileSystemError::CanonicalizeFailed {
-            path: path.to_path_buf(),
-            source: e,
-        })
-        .with_context(|| format!("Failed to resolve absolute path for: {}", path.display()))?;
+    let canonical_path = utils::canonicalize_path(path)?;
 
     match process::Command::new(command).arg(&canonical_path).status() {
         Ok(status) if status.success() => Ok(()),
         Ok(status) => {
-            eprintln!(
-                "Default editor exited with status: {}. Attempting CLI editor...",
-                status
-            );
-            open_with_cli_editor(path)
-                .with_context(|| format!("All editor attempts failed for file: {}", path.display()))
+            console::warning(format!(
+                "Default editor exited with status: {status}. Attempting CLI editor..."
+            ));
+            open_with_fallback(path, fallback_editors)
         }
         Err(e) => {
-            eprintln!(
-                "Error opening file with default editor: {}. Attempting CLI editor...",
-                e
-            );
-            open_with_cli_editor(path)
-                .with_context(|| format!("All editor attempts failed for file: {}", path.display()))
+            console::warning(format!(
+                "Error opening file with default editor: {e}. Attempting CLI editor..."
+            ));
+            open_with_fallback(path, fallback_editors)
         }
     }
 }
@@ -88,6 +103,8 @@ pub fn delete(path: &Path) -> anyhow::Result<()> {
 // -------------------------------------------- Private Helper Functions --------------------------------------------
 
 /// Returns the platform-specific command for opening files.
+///
+/// The macOS check must stay ahead of the generic `unix` one, which macOS also matches.
 fn get_platform_open_command() -> &'static str {
     if cfg!(windows) {
         "start"
@@ -100,42 +117,151 @@ fn get_platform_open_command() -> &'static str {
     }
 }
 
-/// Opens the file using a CLI text editor.
-fn open_with_cli_editor(path: &Path) -> anyhow::Result<()> {
-    let default_cli_editor = env::var("EDITOR").unwrap_or_else(|e| {
-        eprintln!(
-            "Error reading EDITOR environment variable: {}. Falling back to nano.",
-            e
-        );
-        "/bin/nano".to_string()
-    });
+/// Opens the file with the fallback CLI editors, using the system's `PATH` and environment.
+fn open_with_fallback(path: &Path, fallback_editors: &[String]) -> anyhow::Result<()> {
+    let chain: Vec<String> = if fallback_editors.is_empty() {
+        DEFAULT_FALLBACK_EDITORS
+            .iter()
+            .map(|editor| editor.to_string())
+            .collect()
+    } else {
+        fallback_editors.to_vec()
+    };
+    let commands = expand_editor_chain(&chain, |name| env::var(name).ok());
+
+    open_with_cli_editor(path, &commands, &mut SystemRunner)
+        .with_context(|| format!("All editor attempts failed for file: {}", path.display()))
+}
+
+/// Finds and launches editor programs; abstracted so the fallback chain can be tested.
+trait EditorRunner {
+    /// Resolves a program name to an executable, or `None` if it isn't installed.
+    fn resolve(&self, program: &str) -> Option<PathBuf>;
+
+    /// Runs `program` with `args` and waits for it to exit.
+    fn run(
+        &mut self,
+        program: &Path,
+        args: &[&str],
+        path: &Path,
+    ) -> io::Result<process::ExitStatus>;
+}
+
+/// Runs editors as real processes, resolved through `PATH`.
+struct SystemRunner;
+
+impl EditorRunner for SystemRunner {
+    fn resolve(&self, program: &str) -> Option<PathBuf> {
+        find_program(program, env::var_os("PATH").unwrap_or_default())
+    }
 
-    let status = process::Command::new(&default_cli_editor)
-        .arg(path)
-        .status()
-        .map_err(|e| EditorError::OpenFailed {
+    fn run(
+        &mut self,
+        program: &Path,
+        args: &[&str],
+        path: &Path,
+    ) -> io::Result<process::ExitStatus> {
+        process::Command::new(program).args(args).arg(path).status()
+    }
+}
+
+/// Resolves `$NAME` entries of an editor chain from `lookup_env`, dropping unset or empty ones.
+fn expand_editor_chain(
+    chain: &[String],
+    lookup_env: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    chain
+        .iter()
+        .filter_map(|editor| match editor.strip_prefix('$') {
+            Some(name) => lookup_env(name),
+            None => Some(editor.clone()),
+        })
+        .filter(|command| !command.trim().is_empty())
+        .collect()
+}
+
+/// Opens the file with the first of `commands` that resolves and launches.
+///
+/// A command may carry arguments (e.g. `EDITOR="code --wait"`). Editors that
+/// aren't installed or fail to start are skipped; once one starts, its exit
+/// status decides the outcome.
+fn open_with_cli_editor(
+    path: &Path,
+    commands: &[String],
+    runner: &mut impl EditorRunner,
+) -> anyhow::Result<()> {
+    let mut launch_error = None;
+    for command in commands {
+        let mut words = command.split_whitespace();
+        let Some(program) = words.next() else {
+            continue;
+        };
+        let args: Vec<&str> = words.collect();
+        let Some(resolved) = runner.resolve(program) else {
+            continue;
+        };
+
+        let status = match runner.run(&resolved, &args, path) {
+            Ok(status) => status,
+            Err(e) => {
+                console::warning(format!(
+                    "Failed to launch editor '{command}': {e}. Trying the next one..."
+                ));
+                launch_error = Some(e);
+                continue;
+            }
+        };
+        if !status.success() {
+            return Err(EditorError::ProcessFailed { status }.into());
+        }
+        return Ok(());
+    }
+
+    let tried = commands.join(", ");
+    match launch_error {
+        Some(e) => Err(EditorError::OpenFailed {
             path: path.to_path_buf(),
             source: e,
         })
-        .with_context(|| {
-            format!(
-                "Failed to launch editor '{}' for file: {}",
-                default_cli_editor,
-                path.display()
-            )
-        })?;
+        .with_context(|| format!("No fallback editor could be launched (tried: {tried})")),
+        None => Err(EditorError::NoEditorFound(tried).into()),
+    }
+}
 
-    if !status.success() {
-        return Err(EditorError::ProcessFailed { status }.into());
+/// Looks up `program` in the directories of `path_var`, like `which`.
+///
+/// A program given as a path (containing a separator) is only checked for existence.
+fn find_program(program: &str, path_var: OsString) -> Option<PathBuf> {
+    let candidate = Path::new(program);
+    if candidate.components().count() > 1 {
+        return candidate.is_file().then(|| candidate.to_path_buf());
     }
 
-    Ok(())
+    let extensions: &[&str] = if cfg!(windows) {
+        &["exe", "cmd", "bat"]
+    } else {
+        &[]
+    };
+    env::split_paths(&path_var).find_map(|dir| {
+        let path = dir.join(program);
+        if path.is_file() {
+            return Some(path);
+        }
+        extensions
+            .iter()
+            .map(|extension| path.with_extension(extension))
+            .find(|path| path.is_file())
+    })
 }
 
 #[cfg(test)]
 mod editor_tests {
     use super::*;
     use std::fs;
+    #[cfg(unix)]
+    use std::os::unix::process::ExitStatusExt;
+    #[cfg(windows)]
+    use std::os::windows::process::ExitStatusExt;
     use tempfile::TempDir;
 
     #[test]
@@ -153,6 +279,98 @@ mod editor_tests {
         }
     }
 
+    /// macOS is also `unix`, so the `macos` check must come first or it gets `xdg-open`.
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_macos_uses_open_not_xdg_open() {
+        assert_eq!(get_platform_open_command(), "open");
+    }
+
+    /// Pretends only some editors are installed and records which ones were run.
+    struct FakeRunner {
+        installed: Vec<&'static str>,
+        launched: Vec<(PathBuf, Vec<String>)>,
+    }
+
+    impl EditorRunner for FakeRunner {
+        fn resolve(&self, program: &str) -> Option<PathBuf> {
+            self.installed
+                .contains(&program)
+                .then(|| Path::new("/usr/bin").join(program))
+        }
+
+        fn run(
+            &mut self,
+            program: &Path,
+            args: &[&str],
+            _path: &Path,
+        ) -> io::Result<process::ExitStatus> {
+            self.launched.push((
+                program.to_path_buf(),
+                args.iter().map(|arg| arg.to_string()).collect(),
+            ));
+            Ok(process::ExitStatus::from_raw(0))
+        }
+    }
+
+    #[test]
+    fn test_missing_fallback_editor_proceeds_to_next() -> anyhow::Result<()> {
+        let mut runner = FakeRunner {
+            installed: vec!["vi"],
+            launched: Vec::new(),
+        };
+        let commands = ["no-such-editor".to_string(), "vi -R".to_string()];
+
+        open_with_cli_editor(Path::new("bundle.txt"), &commands, &mut runner)?;
+
+        assert_eq!(
+            runner.launched,
+            [(PathBuf::from("/usr/bin/vi"), vec!["-R".to_string()])]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_fallback_editor_installed() {
+        let mut runner = FakeRunner {
+            installed: Vec::new(),
+            launched: Vec::new(),
+        };
+        let commands = ["nano".to_string(), "vi".to_string()];
+
+        let result = open_with_cli_editor(Path::new("bundle.txt"), &commands, &mut runner);
+        let error = format!("{:?}", result.unwrap_err());
+        assert!(error.contains("nano, vi"));
+        assert!(runner.launched.is_empty());
+    }
+
+    #[test]
+    fn test_expand_editor_chain_reads_environment() {
+        let chain: Vec<String> = DEFAULT_FALLBACK_EDITORS
+            .iter()
+            .map(|editor| editor.to_string())
+            .collect();
+        let lookup = |name: &str| (name == "EDITOR").then(|| "hx".to_string());
+
+        assert_eq!(expand_editor_chain(&chain, lookup), ["hx", "nano", "vi"]);
+    }
+
+    #[test]
+    fn test_find_program_searches_path() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bin = temp_dir.path().join("bin");
+        fs::create_dir(&bin)?;
+        fs::write(bin.join("myeditor"), "")?;
+        let path_var = env::join_paths([temp_dir.path(), &bin])?;
+
+        assert_eq!(
+            find_program("myeditor", path_var.clone()),
+            Some(bin.join("myeditor"))
+        );
+        assert_eq!(find_program("missing", path_var), None);
+        Ok(())
+    }
+
     #[test]
     fn test_delete_file() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -179,7 +397,7 @@ mod editor_tests {
 
     #[test]
     fn test_open_with_nonexistent_file() {
-        let result = open(Path::new("/nonexistent/file.txt"));
+        let result = open(Path::new("/nonexistent/file.txt"), None, &[]);
         // This will fail because canonicalize fails on non-existent paths
         assert!(result.is_err());
 
@@ -187,6 +405,26 @@ mod editor_tests {
         assert!(error_msg.contains("Failed to resolve") || error_msg.contains("canonicalize"));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_open_uses_custom_editor_command() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("bundle.txt");
+        fs::write(&file_path, "content")?;
+
+        open(&file_path, Some("true --wait"), &[])?;
+
+        // A failing custom command is final: no default editor or fallback is tried
+        let error = format!("{:?}", open(&file_path, Some("false"), &[]).unwrap_err());
+        assert!(error.contains("Editor command 'false' failed"));
+        let error = format!(
+            "{:?}",
+            open(&file_path, Some("no-such-editor-cmd"), &[]).unwrap_err()
+        );
+        assert!(error.contains("no-such-editor-cmd"));
+        Ok(())
+    }
+
     #[test]
     fn test_delete_with_permission_error() {
         // This test is platform-specific and may not work in all environments
@@ -0,0 +1,188 @@
+//! editorconfig - Reads the tab width a project's `.editorconfig` files set for a file.
+//!
+//! Only `indent_size` and `tab_width` are understood; everything else in the files is
+//! ignored. Lookup follows the EditorConfig rules: every `.editorconfig` from the file's
+//! directory up to the first one declaring `root = true` applies, nearer files and later
+//! sections taking precedence.
+
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// Name of the files holding the settings.
+const EDITORCONFIG_FILE: &str = ".editorconfig";
+
+/// The settings that decide a file's tab width, as read so far.
+#[derive(Debug, Default)]
+struct IndentSettings {
+    indent_size: Option<String>,
+    tab_width: Option<String>,
+}
+
+impl IndentSettings {
+    /// Resolves the tab width: `tab_width`, or a numeric `indent_size`.
+    fn tab_width(&self) -> Option<usize> {
+        let number = |value: &Option<String>| value.as_deref()?.parse::<usize>().ok();
+        number(&self.tab_width)
+            .or_else(|| number(&self.indent_size))
+            .filter(|&width| width > 0)
+    }
+}
+
+/// Returns the tab width the `.editorconfig` files above `path` set for it, if any.
+///
+/// Unreadable `.editorconfig` files are treated as absent.
+pub fn tab_width(path: &Path) -> Option<usize> {
+    // Nearest first, up to (and including) the first file marked as root
+    let mut configs = Vec::new();
+    for dir in path.ancestors().skip(1) {
+        if let Ok(content) = fs::read_to_string(dir.join(EDITORCONFIG_FILE)) {
+            let root = is_root(&content);
+            configs.push((dir, content));
+            if root {
+                break;
+            }
+        }
+    }
+
+    // Applied farthest first, so nearer files override
+    let mut settings = IndentSettings::default();
+    for (dir, content) in configs.iter().rev() {
+        if let Ok(relative) = path.strip_prefix(dir) {
+            apply_sections(content, &relative.to_string_lossy(), &mut settings);
+        }
+    }
+    settings.tab_width()
+}
+
+/// Checks whether an `.editorconfig` declares `root = true` in its preamble.
+fn is_root(content: &str) -> bool {
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .any(|(key, value)| {
+            key.trim().eq_ignore_ascii_case("root") && value.trim().eq_ignore_ascii_case("true")
+        })
+}
+
+/// Applies the indentation keys of every section whose glob matches `relative`.
+fn apply_sections(content: &str, relative: &str, settings: &mut IndentSettings) {
+    let relative = relative.replace('\\', "/");
+    let mut matches = false;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            matches = glob_regex(glob).is_some_and(|regex| regex.is_match(&relative));
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if !matches {
+            continue;
+        }
+        let value = Some(value.trim().to_ascii_lowercase());
+        match key.trim().to_ascii_lowercase().as_str() {
+            "indent_size" => settings.indent_size = value,
+            "tab_width" => settings.tab_width = value,
+            _ => {}
+        }
+    }
+}
+
+/// Translates a section glob into a regex over `/`-separated paths relative to the
+/// `.editorconfig`'s directory.
+///
+/// Supports `*`, `**`, `?`, `[...]`, and `{a,b}`. A glob without a `/` matches a file
+/// name in any subdirectory.
+fn glob_regex(glob: &str) -> Option<Regex> {
+    let anchored = glob.contains('/');
+    let mut pattern = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = glob.trim_start_matches('/').chars().peekable();
+    let mut braces = 0;
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                pattern.push_str(".*");
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            '[' => {
+                pattern.push('[');
+                for class_char in chars.by_ref() {
+                    if class_char == ']' {
+                        break;
+                    }
+                    if class_char == '\\' {
+                        pattern.push('\\');
+                    }
+                    pattern.push(class_char);
+                }
+                pattern.push(']');
+            }
+            '{' => {
+                braces += 1;
+                pattern.push_str("(?:");
+            }
+            '}' if braces > 0 => {
+                braces -= 1;
+                pattern.push(')');
+            }
+            ',' if braces > 0 => pattern.push('|'),
+            _ => pattern.push_str(&regex::escape(&ch.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).ok()
+}
+
+#[cfg(test)]
+mod editorconfig_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_glob_regex() {
+        let matches = |glob: &str, path: &str| glob_regex(glob).unwrap().is_match(path);
+        assert!(matches("*", "src/main.rs"));
+        assert!(matches("*.rs", "src/main.rs"));
+        assert!(!matches("*.rs", "src/main.py"));
+        assert!(matches("*.{js,ts}", "web/app.ts"));
+        assert!(matches("Makefile", "sub/Makefile"));
+        assert!(matches("src/*.rs", "src/main.rs"));
+        assert!(!matches("src/*.rs", "src/core/mod.rs"));
+        assert!(matches("src/**.rs", "src/core/mod.rs"));
+        assert!(matches("[Mm]akefile", "makefile"));
+    }
+
+    #[test]
+    fn test_tab_width_nearest_and_latest_win() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(project.join("web"))?;
+        fs::write(
+            project.join(EDITORCONFIG_FILE),
+            "root = true\n\n[*]\nindent_size = 4\n\n[*.go]\nindent_size = tab\ntab_width = 8\n",
+        )?;
+        fs::write(
+            project.join("web").join(EDITORCONFIG_FILE),
+            "[*.ts]\nindent_size = 2\n",
+        )?;
+        // Above the root file, so it's never consulted
+        fs::write(
+            temp_dir.path().join(EDITORCONFIG_FILE),
+            "[*]\ntab_width = 3\n",
+        )?;
+
+        assert_eq!(tab_width(&project.join("main.rs")), Some(4));
+        assert_eq!(tab_width(&project.join("main.go")), Some(8));
+        assert_eq!(tab_width(&project.join("web/app.ts")), Some(2));
+        assert_eq!(tab_width(&project.join("web/app.js")), Some(4));
+        Ok(())
+    }
+}
@@ -44,14 +44,14 @@
 //!
 //! ```rust
 //! clipboard
-//!     .set_clipboard()
+//!     .set_clipboard_as(ClipboardMime::PlainText)
 //!     .with_context(|| "Failed to copy content to clipboard")?;
 //! ```
 //!
 //! ### 5. Editor Operations
 //!
 //! ```rust
-//! editor::open(path)
+//! editor::open(path, args.editor_cmd.as_deref(), &args.fallback_editors)
 //!     .with_context(|| format!("Failed to open editor for: {}", path.display()))?;
 //! ```
 //!
@@ -161,7 +161,7 @@
 //!
 //! ## Testing Errors
 //!
-//! ```rust
+//! ```rust,ignore
 //! #[test]
 //! fn test_error_handling() -> anyhow::Result<()> {
 //!     let result = risky_operation();
@@ -294,6 +294,10 @@ use std::path::PathBuf;
 use thiserror::Error;
 
 /// Main error type for TreeClip operations.
+///
+/// Production code reports the specific errors below through `anyhow`; this umbrella
+/// only ties them together for the tests.
+#[cfg(test)]
 #[derive(Error, Debug)]
 pub enum TreeClipError {
     /// Error related to clipboard operations.
@@ -316,6 +320,18 @@ pub enum TreeClipError {
     #[error("Pattern error: {0}")]
     Pattern(#[from] PatternError),
 
+    /// Error related to git lookups.
+    #[error("Git error: {0}")]
+    Git(#[from] GitError),
+
+    /// Error related to extracting a bundle.
+    #[error("Unbundle error: {0}")]
+    Unbundle(#[from] UnbundleError),
+
+    /// Error related to the config file.
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+
     /// Generic I/O error with context.
     #[error("I/O error: {message}")]
     Io {
@@ -334,12 +350,8 @@ pub enum ClipboardError {
     #[error("Failed to set clipboard content: {0}")]
     SetFailed(String),
 
-    #[error("Failed to read file for clipboard: {path}")]
-    ReadFailed {
-        path: PathBuf,
-        #[source]
-        source: std::io::Error,
-    },
+    #[error("Failed to read clipboard content: {0}")]
+    GetFailed(String),
 
     #[error("Clipboard content too large: {size} bytes (max: {max} bytes)")]
     ContentTooLarge { size: usize, max: usize },
@@ -386,8 +398,8 @@ pub enum FileSystemError {
         source: std::io::Error,
     },
 
-    #[error("Permission denied: {path}")]
-    PermissionDenied { path: PathBuf },
+    #[error("Refusing to write through symlinked output: {path}")]
+    SymlinkedOutput { path: PathBuf },
 }
 
 /// Errors specific to directory traversal.
@@ -400,18 +412,25 @@ pub enum TraversalError {
         source: walkdir::Error,
     },
 
-    #[error("Failed to access directory entry: {path}")]
-    EntryAccessFailed { path: PathBuf },
-
-    #[error("Failed to write output file: {path}")]
-    OutputWriteFailed {
-        path: PathBuf,
-        #[source]
-        source: std::io::Error,
-    },
-
     #[error("No files found in directory: {0}")]
     NoFilesFound(PathBuf),
+
+    #[error("Included file is empty: {0}")]
+    EmptyFile(PathBuf),
+
+    #[error("Found a likely {kind} in {path}; aborting so it can't leak (--abort-on-secret)")]
+    SecretFound { path: PathBuf, kind: &'static str },
+
+    #[error("Operation timed out after {0} seconds")]
+    TimedOut(f64),
+
+    #[error("File {path} lies outside the root directory {root}")]
+    OutsideRoot { path: PathBuf, root: PathBuf },
+
+    #[error(
+        "--split-by-dir would write both the files directly under {input} and its '{dir}' directory to {dir}.txt"
+    )]
+    SplitGroupClash { input: PathBuf, dir: String },
 }
 
 /// Errors specific to editor operations.
@@ -429,9 +448,6 @@ pub enum EditorError {
 
     #[error("No suitable editor found (tried: {0})")]
     NoEditorFound(String),
-
-    #[error("Failed to get EDITOR environment variable")]
-    NoEditorEnvVar,
 }
 
 /// Errors specific to pattern matching and exclusion.
@@ -444,21 +460,71 @@ pub enum PatternError {
         source: ignore::Error,
     },
 
-    #[error("Failed to read ignore file: {path}")]
-    #[allow(dead_code)]
-    IgnoreFileReadFailed {
-        path: PathBuf,
+    #[error("Failed to build gitignore matcher")]
+    BuildFailed {
+        #[source]
+        source: ignore::Error,
+    },
+}
+
+/// Errors specific to running git commands.
+#[derive(Error, Debug)]
+pub enum GitError {
+    #[error("Failed to run `{command}`")]
+    SpawnFailed {
+        command: String,
         #[source]
         source: std::io::Error,
     },
 
-    #[error("Failed to build gitignore matcher")]
-    BuildFailed {
+    #[error("`{command}` failed: {stderr}")]
+    CommandFailed { command: String, stderr: String },
+}
+
+/// Errors specific to extracting files from a bundle.
+#[derive(Error, Debug)]
+pub enum UnbundleError {
+    #[error("Refusing to write outside the output directory: {0}")]
+    UnsafePath(String),
+
+    #[error("Invalid {encoding} content for: {path}")]
+    DecodeFailed {
+        path: String,
+        encoding: &'static str,
+    },
+}
+
+/// Errors specific to loading the `treeclip.toml` config file.
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Malformed config file: {path}")]
+    Malformed {
+        path: PathBuf,
         #[source]
-        source: ignore::Error,
+        source: toml::de::Error,
+    },
+
+    #[error("Invalid config value for `{key}`: {value} ({reason})")]
+    InvalidValue {
+        key: &'static str,
+        value: String,
+        reason: String,
+    },
+
+    #[error("--{flag} requires --{required}, on the command line or in the config file")]
+    MissingRequirement {
+        flag: &'static str,
+        required: &'static str,
+    },
+
+    #[error("--{flag} can't be combined with --{other}, on the command line or in the config file")]
+    Conflict {
+        flag: &'static str,
+        other: &'static str,
     },
 }
 
+#[cfg(test)]
 impl TreeClipError {
     /// Creates an I/O error with context message.
     pub fn io_with_context(message: impl Into<String>, source: std::io::Error) -> Self {
@@ -494,6 +560,29 @@ mod errors_tests {
         assert!(err.to_string().contains("No files found"));
     }
 
+    #[test]
+    fn test_empty_file_error_display() {
+        let err = TraversalError::EmptyFile(PathBuf::from("/test/empty.txt"));
+        assert!(err.to_string().contains("empty"));
+        assert!(err.to_string().contains("/test/empty.txt"));
+    }
+
+    #[test]
+    fn test_git_error_display() {
+        let err = GitError::CommandFailed {
+            command: "git ls-files".to_string(),
+            stderr: "not a git repository".to_string(),
+        };
+        assert!(err.to_string().contains("git ls-files"));
+        assert!(err.to_string().contains("not a git repository"));
+    }
+
+    #[test]
+    fn test_timed_out_error_display() {
+        let err = TraversalError::TimedOut(2.5);
+        assert!(err.to_string().contains("timed out after 2.5 seconds"));
+    }
+
     #[test]
     fn test_editor_error_display() {
         let err = EditorError::NoEditorFound("vim, nano".to_string());
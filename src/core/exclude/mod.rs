@@ -1,38 +1,209 @@
 //! exclude - Handles file and directory exclusion patterns using gitignore-style rules.
 
 use crate::core::errors::PatternError;
+use crate::core::ui::console;
 use crate::core::ui::messages::Messages;
 use anyhow::Context;
-use ignore::gitignore::{Gitignore, GitignoreBuilder};
-use std::path::Path;
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder, Glob};
+use std::path::{Component, Path, PathBuf};
+use std::{env, fs};
+
+/// Name of TreeClip's own ignore file.
+const TREECLIP_IGNORE_FILE: &str = ".treeclipignore";
+
+/// Name of git's ignore file.
+const GITIGNORE_FILE: &str = ".gitignore";
+
+/// Name of the generic ignore file used by ripgrep and other `ignore`-based tools.
+const DOT_IGNORE_FILE: &str = ".ignore";
+
+/// Lockfile names excluded by `--skip-lockfiles`.
+pub const LOCKFILE_PATTERNS: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "poetry.lock",
+    "Pipfile.lock",
+    "uv.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+    "mix.lock",
+    "pubspec.lock",
+    "Podfile.lock",
+    "flake.lock",
+];
+
+/// Source label used for patterns passed with `--exclude`.
+const CLI_PATTERN_SOURCE: &str = "--exclude";
+
+/// Source label used for the built-in `--skip-lockfiles` patterns.
+const LOCKFILE_PATTERN_SOURCE: &str = "--skip-lockfiles";
 
 /// ExcludeMatcher determines whether paths should be excluded from traversal.
 pub struct ExcludeMatcher {
     inner: Gitignore,
+    /// Every declared pattern as `(source, pattern)`, in precedence order.
+    declared: Vec<(String, String)>,
+    /// CLI patterns kept apart from `inner` so they win outright (`--cli-patterns-override`).
+    cli_override: Option<CliOverride>,
+    /// Ignore files of the root's parent directories: `.treeclipignore` files, then
+    /// `.gitignore` files (`--use-gitignore`), each nearest first.
+    ///
+    /// Their patterns are relative to their own directory, so each gets its own matcher.
+    parent_ignores: Vec<Gitignore>,
+}
+
+/// CLI patterns that take precedence over every ignore file.
+struct CliOverride {
+    rules: Gitignore,
+    /// Re-inclusion (`!pattern`) patterns without the `!`, used to avoid pruning their parents.
+    reinclusions: Vec<String>,
+}
+
+/// How often each declared exclusion pattern matched during traversal.
+///
+/// Only the pattern that decided a path's fate is credited, so a pattern that is
+/// always shadowed by a later one shows up as unused too.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatternStats {
+    entries: Vec<PatternCount>,
+}
+
+/// Match count for a single exclusion pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternCount {
+    /// Where the pattern came from (`.treeclipignore`, `.ignore`, or `--exclude`).
+    pub source: String,
+    /// The pattern as written.
+    pub pattern: String,
+    /// Number of paths the pattern matched.
+    pub matches: usize,
+}
+
+/// Options controlling which ignore sources an `ExcludeMatcher` loads.
+///
+/// Sources are applied in increasing order of precedence (later sources win,
+/// so a later `!pattern` can re-include what an earlier one excluded):
+///
+/// 1. [`LOCKFILE_PATTERNS`] (with `skip_lockfiles`)
+/// 2. `.gitignore` (with `use_gitignore`): the root's own, and below it those of its
+///    parent directories up to the repository's top level, nearer ones winning
+/// 3. `.ignore` (with `use_dot_ignore`)
+/// 4. `.treeclipignore`: the root's own, and below it (with `upward_ignore`) those of its
+///    parent directories, nearer ones winning
+/// 5. CLI patterns
+///
+/// Ignore files of parent directories rank below every source of the root itself.
+/// Even so, a directory excluded by a file pattern is pruned whole, so a CLI
+/// `!dir/file` can't reach back into it. With `cli_patterns_override` CLI
+/// patterns are checked first and decide outright, and a directory that a CLI
+/// re-inclusion spells out (e.g. `build` for `!build/keep.txt`) is still entered.
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeOptions {
+    /// Exclude common lockfiles.
+    pub skip_lockfiles: bool,
+    /// Also load ripgrep-style `.ignore` files from the root.
+    pub use_dot_ignore: bool,
+    /// Also load `.gitignore` files from the root and its parents within the repository.
+    pub use_gitignore: bool,
+    /// Let CLI patterns win over ignore files, even for excluded parent directories.
+    pub cli_patterns_override: bool,
+    /// Also load `.treeclipignore` files from the root's parents, up to the repository's
+    /// top level or the home directory.
+    pub upward_ignore: bool,
+    /// Directory the `upward_ignore` search starts from (the input being walked);
+    /// defaults to the root.
+    pub upward_from: Option<PathBuf>,
 }
 
 impl ExcludeMatcher {
-    /// Creates a new ExcludeMatcher with patterns from .treeclipignore and CLI arguments.
+    /// Creates a new ExcludeMatcher with patterns from .treeclipignore and CLI arguments,
+    /// using the default [`ExcludeOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ExcludeMatcher::with_options`].
+    #[cfg(test)]
+    pub fn new(root: &Path, cli_patterns: &[String]) -> anyhow::Result<Self> {
+        Self::with_options(root, cli_patterns, &ExcludeOptions::default())
+    }
+
+    /// Creates a new ExcludeMatcher, loading the ignore sources selected by `options`.
+    ///
+    /// See [`ExcludeOptions`] for the precedence between sources.
     ///
     /// # Arguments
     ///
     /// * `root` - Root directory to search for .treeclipignore file
     /// * `cli_patterns` - Additional exclusion patterns from command-line arguments
+    /// * `options` - Which ignore sources to load
     ///
     /// # Errors
     ///
     /// Returns `PatternError` if:
     /// - The gitignore builder fails to compile patterns
     /// - Invalid pattern syntax is provided
-    pub fn new(root: &Path, cli_patterns: &[String]) -> anyhow::Result<Self> {
+    pub fn with_options(
+        root: &Path,
+        cli_patterns: &[String],
+        options: &ExcludeOptions,
+    ) -> anyhow::Result<Self> {
         let mut builder = GitignoreBuilder::new(root);
+        let mut declared = Vec::new();
+
+        // Built-in lockfile patterns go first so any explicit rule (e.g. '!Cargo.lock') overrides them
+        if options.skip_lockfiles {
+            for pattern in LOCKFILE_PATTERNS {
+                builder
+                    .add_line(Some(PathBuf::from(LOCKFILE_PATTERN_SOURCE)), pattern)
+                    .map_err(|e| PatternError::InvalidPattern {
+                        pattern: pattern.to_string(),
+                        source: e,
+                    })?;
+                declared.push((LOCKFILE_PATTERN_SOURCE.to_string(), pattern.to_string()));
+            }
+        }
 
-        // Add .treeclipignore file patterns (if exists)
-        Self::add_ignore_file(&mut builder, root)?;
+        // Parent directories' ignore files rank below everything else
+        let mut parent_ignores = if options.upward_ignore {
+            let start = options.upward_from.as_deref().unwrap_or(root);
+            Self::upward_treeclipignores(&mut declared, root, start)
+        } else {
+            Vec::new()
+        };
+
+        // Add .gitignore patterns next
+        if options.use_gitignore {
+            Self::add_ignore_file(&mut builder, &mut declared, root, GITIGNORE_FILE)?;
+            parent_ignores.extend(Self::parent_gitignores(&mut declared, root));
+        }
 
-        // Add CLI patterns
-        Self::add_cli_patterns(&mut builder, cli_patterns)
-            .with_context(|| "Failed to process command-line exclusion patterns")?;
+        // Add .ignore file patterns next so treeclip-specific rules can override them
+        if options.use_dot_ignore {
+            Self::add_ignore_file(&mut builder, &mut declared, root, DOT_IGNORE_FILE)?;
+        }
+
+        // Add .treeclipignore file patterns (if exists)
+        Self::add_ignore_file(&mut builder, &mut declared, root, TREECLIP_IGNORE_FILE)?;
+
+        // Add CLI patterns (or keep them in their own matcher that is consulted first)
+        let cli_override = if options.cli_patterns_override && !cli_patterns.is_empty() {
+            Some(CliOverride::new(root, cli_patterns)?)
+        } else {
+            Self::add_cli_patterns(&mut builder, cli_patterns)
+                .with_context(|| "Failed to process command-line exclusion patterns")?;
+            None
+        };
+        declared.extend(
+            cli_patterns
+                .iter()
+                .map(|pattern| (CLI_PATTERN_SOURCE.to_string(), pattern.clone())),
+        );
 
         let inner = builder
             .build()
@@ -44,33 +215,392 @@ impl ExcludeMatcher {
                 )
             })?;
 
-        Ok(Self { inner })
+        Ok(Self {
+            inner,
+            declared,
+            cli_override,
+            parent_ignores,
+        })
     }
 
     /// Checks if a path should be excluded based on configured patterns.
     pub fn is_excluded(&self, path: &Path) -> bool {
-        self.inner.matched(path, path.is_dir()).is_ignore()
+        self.matched(path).is_ignore()
+    }
+
+    /// Like [`ExcludeMatcher::is_excluded`], but credits the deciding pattern in `stats`.
+    pub fn is_excluded_tallied(&self, path: &Path, stats: &mut PatternStats) -> bool {
+        let matched = self.matched(path);
+        if let Some(glob) = matched.inner() {
+            stats.record(&pattern_source(glob.from()), glob.original());
+        }
+        matched.is_ignore()
+    }
+
+    /// Creates an empty tally listing every declared pattern with zero matches.
+    pub fn pattern_stats(&self) -> PatternStats {
+        let mut stats = PatternStats::default();
+        for (source, pattern) in &self.declared {
+            stats.declare(source, pattern);
+        }
+        stats
+    }
+}
+
+impl PatternStats {
+    /// Returns all tallied patterns in declaration order.
+    pub fn entries(&self) -> &[PatternCount] {
+        &self.entries
+    }
+
+    /// Returns the patterns that never matched anything.
+    pub fn unused(&self) -> impl Iterator<Item = &PatternCount> {
+        self.entries.iter().filter(|entry| entry.matches == 0)
+    }
+
+    /// Adds another tally (e.g. from the next input path) into this one.
+    pub fn merge(&mut self, other: PatternStats) {
+        for entry in other.entries {
+            self.declare(&entry.source, &entry.pattern);
+            if let Some(existing) = self.find_mut(&entry.source, &entry.pattern) {
+                existing.matches += entry.matches;
+            }
+        }
+    }
+}
+
+/// PatternMatcher tests paths against a standalone set of gitignore-style globs.
+///
+/// Unlike [`ExcludeMatcher`] it loads no ignore files; it backs options such as
+/// `--stub` that select files for special treatment rather than exclusion.
+pub struct PatternMatcher {
+    root: PathBuf,
+    inner: Gitignore,
+}
+
+impl PatternMatcher {
+    /// Creates a matcher for `patterns`, resolved relative to `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PatternError` if a pattern is invalid or the matcher cannot be built.
+    pub fn new(root: &Path, patterns: &[String]) -> anyhow::Result<Self> {
+        let mut builder = GitignoreBuilder::new(root);
+        ExcludeMatcher::add_cli_patterns(&mut builder, patterns)?;
+        let inner = builder
+            .build()
+            .map_err(|e| PatternError::BuildFailed { source: e })?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            inner,
+        })
+    }
+
+    /// Checks whether the path, or any of its parent directories, matches a pattern.
+    pub fn is_match(&self, path: &Path) -> bool {
+        let is_dir = path.is_dir();
+        let matched = if path.starts_with(&self.root) {
+            self.inner.matched_path_or_any_parents(path, is_dir)
+        } else {
+            self.inner.matched(path, is_dir)
+        };
+        matched.is_ignore()
+    }
+}
+
+/// IncludeMatcher restricts collection to files matching an allowlist (`--include`).
+///
+/// Only files are checked: directories are always entered, so `*.rs` finds Rust
+/// files at any depth. Exclusions still apply on top, so a file must match an
+/// include pattern and no exclude pattern. With no patterns every file is included.
+pub struct IncludeMatcher {
+    patterns: Option<PatternMatcher>,
+    /// One matcher per pattern, in flag order, for [`IncludeMatcher::first_match`].
+    each: Vec<PatternMatcher>,
+}
+
+impl IncludeMatcher {
+    /// Creates a matcher for the include `patterns`, resolved relative to `root`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`PatternMatcher::new`].
+    pub fn new(root: &Path, patterns: &[String]) -> anyhow::Result<Self> {
+        let each = patterns
+            .iter()
+            .map(|pattern| PatternMatcher::new(root, std::slice::from_ref(pattern)))
+            .collect::<anyhow::Result<_>>()?;
+        let patterns = if patterns.is_empty() {
+            None
+        } else {
+            Some(PatternMatcher::new(root, patterns)?)
+        };
+        Ok(Self { patterns, each })
+    }
+
+    /// Returns the index of the first pattern matching `path`, for `--order-by-include`.
+    pub fn first_match(&self, path: &Path) -> Option<usize> {
+        self.each.iter().position(|pattern| pattern.is_match(path))
     }
+
+    /// Checks whether a file should be collected; directories always pass.
+    pub fn is_included(&self, path: &Path, is_dir: bool) -> bool {
+        is_dir
+            || self
+                .patterns
+                .as_ref()
+                .is_none_or(|patterns| patterns.is_match(path))
+    }
+}
+
+/// Selects the exclusion patterns that apply to a single input path.
+///
+/// Patterns may be scoped to one input with an `<input>:<pattern>` prefix, e.g.
+/// `frontend:*.test.ts` only applies while traversing the `frontend` input. The
+/// prefix matches either the input path as given or its final path component.
+/// Unprefixed patterns (and patterns whose prefix names no input) stay global.
+pub fn patterns_for_input(
+    patterns: &[String],
+    input: &Path,
+    all_inputs: &[PathBuf],
+) -> Vec<String> {
+    patterns
+        .iter()
+        .filter_map(|pattern| match split_scoped_pattern(pattern, all_inputs) {
+            Some((scope, scoped)) if scope_matches(scope, input) => Some(scoped.to_string()),
+            Some(_) => None,
+            None => Some(pattern.clone()),
+        })
+        .collect()
 }
 
 // -------------------------------------------- Private Helper Functions --------------------------------------------
 
+/// Splits an `<input>:<pattern>` into its parts if the prefix names one of the inputs.
+fn split_scoped_pattern<'a>(
+    pattern: &'a str,
+    all_inputs: &[PathBuf],
+) -> Option<(&'a str, &'a str)> {
+    let (scope, scoped) = pattern.split_once(':')?;
+    if scoped.is_empty() || !all_inputs.iter().any(|input| scope_matches(scope, input)) {
+        return None;
+    }
+    Some((scope, scoped))
+}
+
+/// Checks whether a scope prefix refers to the given input path.
+fn scope_matches(scope: &str, input: &Path) -> bool {
+    let scope = scope.trim_end_matches(['/', '\\']);
+    if scope.is_empty() {
+        return false;
+    }
+    input == Path::new(scope) || input.file_name().is_some_and(|name| name == scope)
+}
+
+/// Labels a pattern by the ignore file (or built-in set) it came from, or `--exclude` for
+/// CLI patterns.
+fn pattern_source(from: Option<&Path>) -> String {
+    from.and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| CLI_PATTERN_SOURCE.to_string())
+}
+
+/// Lists the patterns declared in an ignore file (blank lines and comments skipped).
+fn declared_patterns(ignore_file: &Path) -> Vec<String> {
+    fs::read_to_string(ignore_file)
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim_end)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl PatternStats {
+    /// Registers a pattern with zero matches unless it's already tallied.
+    fn declare(&mut self, source: &str, pattern: &str) {
+        if self.find_mut(source, pattern).is_none() {
+            self.entries.push(PatternCount {
+                source: source.to_string(),
+                pattern: pattern.to_string(),
+                matches: 0,
+            });
+        }
+    }
+
+    /// Credits one match to a pattern, registering it if needed.
+    fn record(&mut self, source: &str, pattern: &str) {
+        self.declare(source, pattern);
+        if let Some(entry) = self.find_mut(source, pattern) {
+            entry.matches += 1;
+        }
+    }
+
+    fn find_mut(&mut self, source: &str, pattern: &str) -> Option<&mut PatternCount> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.source == source && entry.pattern == pattern)
+    }
+}
+
 impl ExcludeMatcher {
-    /// Adds patterns from .treeclipignore file if it exists.
-    fn add_ignore_file(builder: &mut GitignoreBuilder, root: &Path) -> anyhow::Result<()> {
-        let ignore_file = root.join(".treeclipignore");
+    /// Finds the pattern that decides whether `path` is excluded, if any.
+    fn matched(&self, path: &Path) -> Match<&Glob> {
+        let is_dir = path.is_dir();
+        let Some(cli) = &self.cli_override else {
+            return self.file_rules_matched(path, is_dir, false);
+        };
+
+        let matched = cli.rules.matched(path, is_dir);
+        if !matched.is_none() {
+            return matched;
+        }
+
+        let root = self.inner.path();
+        if is_dir && cli.reaches_into(path.strip_prefix(root).unwrap_or(path)) {
+            return Match::None;
+        }
+
+        // Directories a re-inclusion reaches into aren't pruned, so check their files' parents too
+        self.file_rules_matched(path, is_dir, path.starts_with(root))
+    }
+
+    /// Matches `path` against the ignore-file rules, falling back to parent directories' ones.
+    ///
+    /// With `any_parents`, a match on one of the path's parent directories counts too.
+    fn file_rules_matched(&self, path: &Path, is_dir: bool, any_parents: bool) -> Match<&Glob> {
+        // Parent ignore files are rooted at absolute directories, so match an absolute spelling
+        let absolute = match self.parent_ignores.is_empty() {
+            true => None,
+            false => std::path::absolute(path).ok(),
+        };
+        for (index, rules) in std::iter::once(&self.inner)
+            .chain(&self.parent_ignores)
+            .enumerate()
+        {
+            let path = match (index, &absolute) {
+                (1.., Some(absolute)) => absolute.as_path(),
+                _ => path,
+            };
+            let matched = if any_parents && path.starts_with(rules.path()) {
+                rules.matched_path_or_any_parents(path, is_dir)
+            } else {
+                rules.matched(path, is_dir)
+            };
+            if !matched.is_none() {
+                return matched;
+            }
+        }
+        Match::None
+    }
+
+    /// Loads the `.gitignore` files of `root`'s parents, up to the repository's top level.
+    ///
+    /// Outside a git repository nothing above the root is loaded, so stray ignore files
+    /// (e.g. in the home directory) never apply.
+    fn parent_gitignores(declared: &mut Vec<(String, String)>, root: &Path) -> Vec<Gitignore> {
+        let Some(top) = root.ancestors().find(|dir| dir.join(".git").exists()) else {
+            return Vec::new();
+        };
+
+        let mut gitignores = Vec::new();
+        for dir in root.ancestors().skip(1) {
+            if !dir.starts_with(top) {
+                break;
+            }
+            let ignore_file = dir.join(GITIGNORE_FILE);
+            if ignore_file.is_file() {
+                console::line(Messages::found_ignore_file(
+                    &ignore_file.display().to_string(),
+                ));
+                // Like the root's ignore files, unparseable lines are skipped
+                let (gitignore, _) = Gitignore::new(&ignore_file);
+                gitignores.push(gitignore);
+                declared.extend(
+                    declared_patterns(&ignore_file)
+                        .into_iter()
+                        .map(|pattern| (GITIGNORE_FILE.to_string(), pattern)),
+                );
+            }
+        }
+        gitignores
+    }
+
+    /// Loads the `.treeclipignore` files above `start` (the input being walked), so a run
+    /// from a subdirectory still honors the project's.
+    ///
+    /// Both paths are canonicalized first, so a relative root like `src` still finds the
+    /// files of its parents. The root's own file is loaded with the other root sources, not
+    /// here. The search stops after the repository's top level (the first directory
+    /// holding `.git`) and never reaches the home directory, whose files are personal rather
+    /// than the project's.
+    fn upward_treeclipignores(
+        declared: &mut Vec<(String, String)>,
+        root: &Path,
+        start: &Path,
+    ) -> Vec<Gitignore> {
+        let root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let start = match fs::canonicalize(start) {
+            // A single-file input is searched from its directory
+            Ok(start) if start.is_file() => start.parent().map_or(start.clone(), Path::to_path_buf),
+            Ok(start) => start,
+            Err(_) => root.clone(),
+        };
+        let home = env::var_os("HOME").map(PathBuf::from);
+        let mut ignores = Vec::new();
+        for dir in start.ancestors() {
+            if home.as_deref() == Some(dir) {
+                break;
+            }
+            let ignore_file = dir.join(TREECLIP_IGNORE_FILE);
+            if dir != root && ignore_file.is_file() {
+                console::line(Messages::found_ignore_file(
+                    &ignore_file.display().to_string(),
+                ));
+                // Like the root's ignore files, unparseable lines are skipped
+                let (ignore, _) = Gitignore::new(&ignore_file);
+                ignores.push(ignore);
+                declared.extend(
+                    declared_patterns(&ignore_file)
+                        .into_iter()
+                        .map(|pattern| (TREECLIP_IGNORE_FILE.to_string(), pattern)),
+                );
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        ignores
+    }
+
+    /// Adds patterns from the named ignore file in `root` if it exists.
+    fn add_ignore_file(
+        builder: &mut GitignoreBuilder,
+        declared: &mut Vec<(String, String)>,
+        root: &Path,
+        file_name: &str,
+    ) -> anyhow::Result<()> {
+        let ignore_file = root.join(file_name);
 
         // TODO: Path operations are not concurrent-safe - consider locking or TOCTOU handling
         // See: https://doc.rust-lang.org/stable/std/fs/index.html (TOCTOU section)
         if ignore_file.exists() {
-            println!(
-                "{}",
-                Messages::found_ignore_file(&ignore_file.display().to_string())
-            );
-            println!("{}", Messages::applying_ignore_rules());
+            console::line(Messages::found_ignore_file(
+                &ignore_file.display().to_string(),
+            ));
+            console::line(Messages::applying_ignore_rules(file_name));
 
             // Add with error handling
             builder.add(&ignore_file);
+            declared.extend(
+                declared_patterns(&ignore_file)
+                    .into_iter()
+                    .map(|pattern| (file_name.to_string(), pattern)),
+            );
         }
 
         Ok(())
@@ -100,9 +630,65 @@ impl ExcludeMatcher {
     }
 }
 
+impl CliOverride {
+    fn new(root: &Path, cli_patterns: &[String]) -> anyhow::Result<Self> {
+        let mut builder = GitignoreBuilder::new(root);
+        ExcludeMatcher::add_cli_patterns(&mut builder, cli_patterns)
+            .with_context(|| "Failed to process command-line exclusion patterns")?;
+        let rules = builder
+            .build()
+            .map_err(|e| PatternError::BuildFailed { source: e })?;
+        let reinclusions = cli_patterns
+            .iter()
+            .filter_map(|pattern| pattern.strip_prefix('!'))
+            .map(|pattern| pattern.trim_start_matches('/').to_string())
+            .collect();
+
+        Ok(Self {
+            rules,
+            reinclusions,
+        })
+    }
+
+    /// Checks whether a re-inclusion pattern names something inside the directory `relative`.
+    ///
+    /// Only patterns with a directory part count: `!build/keep.txt` reaches into `build`,
+    /// but a bare `!keep.txt` doesn't reach into every excluded directory.
+    fn reaches_into(&self, relative: &Path) -> bool {
+        let dir: Vec<_> = relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy()),
+                _ => None,
+            })
+            .collect();
+
+        self.reinclusions.iter().any(|pattern| {
+            let parts: Vec<&str> = pattern.trim_end_matches('/').split('/').collect();
+            if parts.len() < 2 {
+                return false;
+            }
+            for (index, name) in dir.iter().enumerate() {
+                match parts.get(index) {
+                    Some(&"**") => return true,
+                    Some(part) if index + 1 < parts.len() => {
+                        let is_glob = part.contains(['*', '?', '[']);
+                        if !is_glob && part != name {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+            true
+        })
+    }
+}
+
 #[cfg(test)]
 mod exclude_tests {
     use super::*;
+    use crate::core::utils;
     use std::fs;
     use tempfile::TempDir;
 
@@ -117,6 +703,33 @@ mod exclude_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_cli_patterns_override_reincludes_from_excluded_dir() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("build"))?;
+        fs::write(root.join("build").join("keep.txt"), "keep")?;
+        fs::write(root.join("build").join("other.txt"), "other")?;
+        fs::write(root.join(TREECLIP_IGNORE_FILE), "build/\n")?;
+
+        let cli_patterns = vec!["!build/keep.txt".to_string()];
+
+        // By default the whole directory is pruned, so the re-inclusion never applies
+        let default = ExcludeMatcher::new(root, &cli_patterns)?;
+        assert!(default.is_excluded(&root.join("build")));
+
+        let options = ExcludeOptions {
+            cli_patterns_override: true,
+            ..Default::default()
+        };
+        let matcher = ExcludeMatcher::with_options(root, &cli_patterns, &options)?;
+        assert!(!matcher.is_excluded(&root.join("build")));
+        assert!(!matcher.is_excluded(&root.join("build").join("keep.txt")));
+        assert!(matcher.is_excluded(&root.join("build").join("other.txt")));
+
+        Ok(())
+    }
+
     #[test]
     fn test_is_excluded_with_ignore_file() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -203,6 +816,160 @@ mod exclude_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dot_ignore_file_requires_option() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        let vendor = root.join("vendor");
+        fs::create_dir(&vendor)?;
+        fs::write(root.join(".ignore"), "vendor/")?;
+
+        // Not loaded by default
+        let matcher = ExcludeMatcher::new(root, &[])?;
+        assert!(!matcher.is_excluded(&vendor));
+
+        let options = ExcludeOptions {
+            use_dot_ignore: true,
+            ..Default::default()
+        };
+        let matcher = ExcludeMatcher::with_options(root, &[], &options)?;
+        assert!(matcher.is_excluded(&vendor));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_treeclipignore_overrides_dot_ignore() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        let docs = root.join("docs");
+        fs::create_dir(&docs)?;
+        fs::write(root.join(".ignore"), "docs/")?;
+        fs::write(root.join(".treeclipignore"), "!docs/")?;
+
+        let options = ExcludeOptions {
+            use_dot_ignore: true,
+            ..Default::default()
+        };
+        let matcher = ExcludeMatcher::with_options(root, &[], &options)?;
+        assert!(!matcher.is_excluded(&docs));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gitignore_composes_with_treeclipignore() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("build"))?;
+        fs::create_dir(root.join("docs"))?;
+        fs::write(root.join(GITIGNORE_FILE), "build/\n*.log\n")?;
+        fs::write(root.join(TREECLIP_IGNORE_FILE), "docs/\n!keep.log\n")?;
+
+        let options = ExcludeOptions {
+            use_gitignore: true,
+            ..Default::default()
+        };
+        let matcher = ExcludeMatcher::with_options(root, &["!debug.log".to_string()], &options)?;
+        assert!(matcher.is_excluded(&root.join("build")));
+        assert!(matcher.is_excluded(&root.join("docs")));
+        assert!(matcher.is_excluded(&root.join("trace.log")));
+        // .treeclipignore and CLI patterns take precedence over .gitignore
+        assert!(!matcher.is_excluded(&root.join("keep.log")));
+        assert!(!matcher.is_excluded(&root.join("debug.log")));
+
+        // Without the option .gitignore is ignored
+        let default = ExcludeMatcher::new(root, &[])?;
+        assert!(!default.is_excluded(&root.join("build")));
+        assert!(default.is_excluded(&root.join("docs")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gitignore_loads_parents_up_to_repository_top() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let above = temp_dir.path();
+        let repo = above.join("repo");
+        let root = repo.join("app");
+        fs::create_dir_all(repo.join(".git"))?;
+        fs::create_dir_all(&root)?;
+        fs::write(above.join(GITIGNORE_FILE), "*.rs\n")?;
+        fs::write(repo.join(GITIGNORE_FILE), "*.log\n/app/generated/\n")?;
+        fs::create_dir(root.join("generated"))?;
+
+        let options = ExcludeOptions {
+            use_gitignore: true,
+            ..Default::default()
+        };
+        let matcher = ExcludeMatcher::with_options(&root, &[], &options)?;
+        assert!(matcher.is_excluded(&root.join("server.log")));
+        // Anchored patterns resolve against their own .gitignore's directory
+        assert!(matcher.is_excluded(&root.join("generated")));
+        // Nothing above the repository's top level applies
+        assert!(!matcher.is_excluded(&root.join("main.rs")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_treeclipignore_found_up_the_tree() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let above = temp_dir.path();
+        let repo = above.join("repo");
+        let root = repo.join("crates").join("app");
+        fs::create_dir_all(repo.join(".git"))?;
+        fs::create_dir_all(&root)?;
+        fs::write(above.join(TREECLIP_IGNORE_FILE), "*.rs\n")?;
+        fs::write(repo.join(TREECLIP_IGNORE_FILE), "*.log\n/crates/app/gen/\n")?;
+        fs::create_dir(root.join("gen"))?;
+
+        let options = ExcludeOptions {
+            upward_ignore: true,
+            ..Default::default()
+        };
+        let matcher = ExcludeMatcher::with_options(&root, &[], &options)?;
+        assert!(matcher.is_excluded(&root.join("server.log")));
+        assert!(matcher.is_excluded(&root.join("gen")));
+        // The search stops at the repository's top level
+        assert!(!matcher.is_excluded(&root.join("main.rs")));
+
+        // --no-upward-ignore leaves only the root's own ignore file
+        let matcher = ExcludeMatcher::with_options(&root, &[], &ExcludeOptions::default())?;
+        assert!(!matcher.is_excluded(&root.join("server.log")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_treeclipignore_found_above_relative_root() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo = temp_dir.path().join("repo");
+        fs::create_dir_all(repo.join(".git"))?;
+        fs::create_dir_all(repo.join("app").join("src").join("gen"))?;
+        fs::write(repo.join(TREECLIP_IGNORE_FILE), "*.log\n/app/src/gen/\n")?;
+
+        // `--root app` run from the repository: the root's spelling has no parents
+        let _cwd = utils::CWD_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let previous = env::current_dir()?;
+        env::set_current_dir(&repo)?;
+        let root = Path::new("app");
+        let options = ExcludeOptions {
+            upward_ignore: true,
+            upward_from: Some(root.join("src")),
+            ..Default::default()
+        };
+        let excluded = ExcludeMatcher::with_options(root, &[], &options).map(|matcher| {
+            ["app/src/server.log", "app/src/gen", "app/src/main.rs"]
+                .map(|path| matcher.is_excluded(Path::new(path)))
+        });
+        env::set_current_dir(previous)?;
+
+        assert_eq!(excluded?, [true, true, false]);
+        Ok(())
+    }
+
     #[test]
     fn test_invalid_pattern_error() {
         let temp_dir = TempDir::new().unwrap();
@@ -253,6 +1020,23 @@ mod exclude_tests {
         Ok(())
     }
 
+    #[test]
+    fn test_patterns_for_input_scoping() {
+        let inputs = vec![PathBuf::from("frontend"), PathBuf::from("/work/backend")];
+        let patterns = vec![
+            "frontend:*.test.ts".to_string(),
+            "backend/:*.snap".to_string(),
+            "*.log".to_string(),
+            "c:*.tmp".to_string(), // prefix names no input, stays a global pattern
+        ];
+
+        let frontend = patterns_for_input(&patterns, &inputs[0], &inputs);
+        assert_eq!(frontend, vec!["*.test.ts", "*.log", "c:*.tmp"]);
+
+        let backend = patterns_for_input(&patterns, &inputs[1], &inputs);
+        assert_eq!(backend, vec!["*.snap", "*.log", "c:*.tmp"]);
+    }
+
     #[test]
     fn test_wildcard_cli_patterns() -> anyhow::Result<()> {
         let temp_dir = TempDir::new()?;
@@ -283,4 +1067,98 @@ mod exclude_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_pattern_stats_flags_unused_patterns() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        fs::create_dir(root.join("node_modules"))?;
+        fs::write(root.join("a.log"), "")?;
+        fs::write(root.join("b.log"), "")?;
+        fs::write(
+            root.join(".treeclipignore"),
+            "# deps\nnode_modules\nbower_components\n",
+        )?;
+
+        let matcher = ExcludeMatcher::new(root, &["*.log".to_string()])?;
+        let mut stats = matcher.pattern_stats();
+        for entry in fs::read_dir(root)? {
+            matcher.is_excluded_tallied(&entry?.path(), &mut stats);
+        }
+
+        let count_of = |pattern: &str| {
+            stats
+                .entries()
+                .iter()
+                .find(|entry| entry.pattern == pattern)
+                .map(|entry| entry.matches)
+        };
+        assert_eq!(count_of("node_modules"), Some(1));
+        assert_eq!(count_of("*.log"), Some(2));
+        assert_eq!(count_of("bower_components"), Some(0));
+
+        let unused: Vec<_> = stats.unused().map(|entry| entry.pattern.as_str()).collect();
+        assert_eq!(unused, vec!["bower_components"]);
+
+        // Merging tallies from another input sums the counts
+        let mut total = stats.clone();
+        total.merge(stats);
+        assert_eq!(
+            total
+                .entries()
+                .iter()
+                .map(|entry| entry.matches)
+                .sum::<usize>(),
+            6
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_include_matcher_combines_with_exclusions() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src"))?;
+        fs::create_dir_all(root.join("target").join("debug"))?;
+
+        let include = IncludeMatcher::new(root, &["*.rs".to_string()])?;
+        let exclude = ExcludeMatcher::new(root, &["target".to_string()])?;
+        let collected = |path: &Path, is_dir: bool| {
+            include.is_included(path, is_dir) && !exclude.is_excluded(path)
+        };
+
+        assert!(collected(&root.join("src").join("main.rs"), false));
+        assert!(collected(&root.join("src"), true));
+        assert!(!collected(&root.join("Cargo.toml"), false));
+        // Excluded directories stay pruned even though they'd hold included files
+        assert!(!collected(&root.join("target"), true));
+
+        // No include patterns: everything passes
+        let everything = IncludeMatcher::new(root, &[])?;
+        assert!(everything.is_included(&root.join("Cargo.toml"), false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pattern_matcher_matches_files_and_parents() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("vendor/lib"))?;
+        fs::write(root.join("vendor/lib/big.js"), "")?;
+        fs::write(root.join("data.csv"), "")?;
+        fs::write(root.join("main.rs"), "")?;
+
+        let matcher = PatternMatcher::new(root, &["vendor/".to_string(), "*.csv".to_string()])?;
+        assert!(matcher.is_match(&root.join("vendor/lib/big.js")));
+        assert!(matcher.is_match(&root.join("data.csv")));
+        assert!(!matcher.is_match(&root.join("main.rs")));
+
+        // Paths outside the root don't panic
+        assert!(!matcher.is_match(Path::new("/elsewhere/main.rs")));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,336 @@
+//! git - Queries git for its view of the files being bundled.
+//!
+//! All lookups shell out to the `git` binary, so they reflect the repository's own
+//! configuration (`.gitattributes`, `core.autocrlf`, LFS filters, ...).
+
+use crate::core::errors::GitError;
+use anyhow::Context;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Returns the files under `dir` that git considers text, as paths joined onto `dir`.
+///
+/// A file counts as text unless git detects it as binary (`-text` content), a
+/// `.gitattributes` rule marks it `-text`/`binary`, or it is stored through Git LFS
+/// (`filter=lfs`). Untracked, non-ignored files are judged by their worktree content.
+///
+/// Returns `Ok(None)` if `dir` is not inside a git work tree.
+///
+/// # Errors
+///
+/// Returns `GitError` if git cannot be run or one of its commands fails.
+pub fn text_files(dir: &Path) -> anyhow::Result<Option<HashSet<PathBuf>>> {
+    if !is_work_tree(dir) {
+        return Ok(None);
+    }
+
+    let listing = run_git(
+        dir,
+        &[
+            "ls-files",
+            "-z",
+            "--eol",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ],
+        None,
+    )
+    .with_context(|| format!("Failed to list files tracked by git in: {}", dir.display()))?;
+
+    let candidates: Vec<&str> = listing
+        .split('\0')
+        .filter_map(parse_eol_entry)
+        .filter(|(info, _)| is_text_eol_info(info))
+        .map(|(_, path)| path)
+        .collect();
+
+    let lfs_files = lfs_files(dir, &candidates)?;
+
+    Ok(Some(
+        candidates
+            .into_iter()
+            .filter(|path| !lfs_files.contains(*path))
+            .map(|path| dir.join(path))
+            .collect(),
+    ))
+}
+
+/// The last commit that touched a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastCommit {
+    pub author: String,
+    /// Commit date as `YYYY-MM-DD`.
+    pub date: String,
+}
+
+/// Returns the last commit touching each file tracked under `dir`, keyed by paths joined
+/// onto `dir`.
+///
+/// A single `git log` pass covers every file, so bundling a large tree costs one process
+/// rather than one per file.
+///
+/// Returns `Ok(None)` if `dir` is not inside a git work tree.
+///
+/// # Errors
+///
+/// Returns `GitError` if git cannot be run or `git log` fails.
+pub fn last_commits(dir: &Path) -> anyhow::Result<Option<HashMap<PathBuf, LastCommit>>> {
+    if !is_work_tree(dir) {
+        return Ok(None);
+    }
+
+    // Newest first: each commit is a `\x01<author>\t<date>` record followed by its files
+    let log = run_git(
+        dir,
+        &[
+            "log",
+            "-z",
+            "--relative",
+            "--name-only",
+            "--format=%x01%an%x09%as",
+            "--",
+            ".",
+        ],
+        None,
+    )
+    .with_context(|| format!("Failed to read git history in: {}", dir.display()))?;
+
+    let mut commits = HashMap::new();
+    let mut current: Option<LastCommit> = None;
+    for record in log.split('\0') {
+        if let Some(header) = record.strip_prefix('\x01') {
+            current = header.split_once('\t').map(|(author, date)| LastCommit {
+                author: author.to_string(),
+                date: date.to_string(),
+            });
+            continue;
+        }
+        let path = record.trim_start_matches('\n');
+        if let Some(commit) = &current
+            && !path.is_empty()
+        {
+            commits
+                .entry(dir.join(path))
+                .or_insert_with(|| commit.clone());
+        }
+    }
+    Ok(Some(commits))
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Checks whether `dir` is inside a git work tree (false if git isn't installed).
+fn is_work_tree(dir: &Path) -> bool {
+    run_git(dir, &["rev-parse", "--is-inside-work-tree"], None)
+        .is_ok_and(|output| output.trim() == "true")
+}
+
+/// Returns the subset of `paths` stored through Git LFS.
+fn lfs_files<'a>(dir: &Path, paths: &[&'a str]) -> anyhow::Result<HashSet<&'a str>> {
+    if paths.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let stdin: String = paths.iter().map(|path| format!("{path}\0")).collect();
+    let output = run_git(
+        dir,
+        &["check-attr", "-z", "--stdin", "filter"],
+        Some(&stdin),
+    )
+    .with_context(|| format!("Failed to read git attributes in: {}", dir.display()))?;
+
+    // Output is a flat list of NUL-terminated `<path> <attribute> <value>` triples
+    let fields: Vec<&str> = output.split('\0').collect();
+    Ok(fields
+        .chunks_exact(3)
+        .filter(|triple| triple[2] == "lfs")
+        .filter_map(|triple| paths.iter().find(|path| **path == triple[0]).copied())
+        .collect())
+}
+
+/// Splits one `git ls-files --eol` record into its info columns and path.
+fn parse_eol_entry(record: &str) -> Option<(&str, &str)> {
+    let (info, path) = record.split_once('\t')?;
+    (!path.is_empty()).then_some((info, path))
+}
+
+/// Interprets the `i/<eol> w/<eol> attr/<attrs>` columns of `git ls-files --eol`.
+///
+/// The index column decides for tracked files; untracked files only have the worktree one.
+fn is_text_eol_info(info: &str) -> bool {
+    let mut columns = info.split_whitespace();
+    let index = columns
+        .next()
+        .and_then(|c| c.strip_prefix("i/"))
+        .unwrap_or("");
+    let worktree = columns
+        .next()
+        .and_then(|c| c.strip_prefix("w/"))
+        .unwrap_or("");
+    let attrs = columns.collect::<Vec<_>>();
+
+    let detected = if index.is_empty() { worktree } else { index };
+    let binary_by_attr = attrs
+        .iter()
+        .any(|attr| *attr == "attr/-text" || *attr == "-text");
+    detected != "-text" && !binary_by_attr
+}
+
+/// Runs a git command in `dir`, optionally feeding `stdin`, and returns its stdout.
+fn run_git(dir: &Path, args: &[&str], stdin: Option<&str>) -> anyhow::Result<String> {
+    let command = format!("git {}", args.join(" "));
+
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| GitError::SpawnFailed {
+            command: command.clone(),
+            source: e,
+        })?;
+
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(input.as_bytes())
+            .map_err(|e| GitError::SpawnFailed {
+                command: command.clone(),
+                source: e,
+            })?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| GitError::SpawnFailed {
+            command: command.clone(),
+            source: e,
+        })?;
+
+    if !output.status.success() {
+        return Err(GitError::CommandFailed {
+            command,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }
+        .into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod git_tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Returns `true` if a usable git binary is on the PATH.
+    fn git_available() -> bool {
+        Command::new("git").arg("--version").output().is_ok()
+    }
+
+    #[test]
+    fn test_is_text_eol_info() {
+        assert!(is_text_eol_info("i/lf    w/lf    attr/                 "));
+        assert!(is_text_eol_info("i/      w/crlf  attr/                 "));
+        assert!(!is_text_eol_info("i/-text w/-text attr/                 "));
+        assert!(!is_text_eol_info("i/lf    w/lf    attr/-text           "));
+    }
+
+    #[test]
+    fn test_non_repo_returns_none() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        assert!(text_files(temp_dir.path())?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_files_skip_binary_and_lfs() -> anyhow::Result<()> {
+        if !git_available() {
+            return Ok(());
+        }
+
+        let temp_dir = TempDir::new()?;
+        let repo = temp_dir.path();
+        run_git(repo, &["init", "-q"], None)?;
+
+        fs::write(
+            repo.join(".gitattributes"),
+            "*.bin filter=lfs diff=lfs merge=lfs\n",
+        )?;
+        fs::write(repo.join("notes.txt"), "plain text\n")?;
+        fs::write(repo.join("image.dat"), [0u8, 159, 146, 150, 0, 1, 2])?;
+        fs::write(
+            repo.join("model.bin"),
+            "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 12\n",
+        )?;
+        run_git(repo, &["add", "notes.txt", "image.dat"], None)?;
+
+        let text = text_files(repo)?.expect("temp dir is a git repo");
+        assert!(text.contains(&repo.join("notes.txt")));
+        assert!(text.contains(&repo.join(".gitattributes")));
+        assert!(!text.contains(&repo.join("image.dat")));
+        assert!(!text.contains(&repo.join("model.bin")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_last_commits_keeps_newest_per_file() -> anyhow::Result<()> {
+        if !git_available() {
+            return Ok(());
+        }
+
+        let temp_dir = TempDir::new()?;
+        let repo = temp_dir.path();
+        run_git(repo, &["init", "-q"], None)?;
+        let commit = |author: &str, date: &str| {
+            run_git(
+                repo,
+                &[
+                    "-c",
+                    &format!("user.name={author}"),
+                    "-c",
+                    "user.email=dev@example.com",
+                    "commit",
+                    "-q",
+                    "-m",
+                    "change",
+                    "--date",
+                    date,
+                ],
+                None,
+            )
+        };
+
+        fs::create_dir_all(repo.join("src"))?;
+        fs::write(repo.join("src/lib.rs"), "v1\n")?;
+        fs::write(repo.join("src/two words.rs"), "v1\n")?;
+        run_git(repo, &["add", "."], None)?;
+        commit("Ann Dev", "2024-01-02T10:00:00")?;
+        fs::write(repo.join("src/lib.rs"), "v2\n")?;
+        run_git(repo, &["add", "."], None)?;
+        commit("Bob", "2024-03-04T10:00:00")?;
+
+        let src = repo.join("src");
+        let commits = last_commits(&src)?.expect("temp dir is a git repo");
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[&src.join("lib.rs")].author, "Bob");
+        assert_eq!(
+            commits[&src.join("two words.rs")],
+            LastCommit {
+                author: "Ann Dev".to_string(),
+                date: "2024-01-02".to_string(),
+            }
+        );
+        Ok(())
+    }
+}
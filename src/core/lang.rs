@@ -0,0 +1,142 @@
+//! lang - Maps file extensions to the language names used for syntax hints.
+//!
+//! The names follow the identifiers Markdown renderers accept after a code fence
+//! (e.g. `rust`, `python`, `typescript`), so they can be used directly as fence
+//! info strings or as language labels in other output formats.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Extension → language pairs, built into a lookup map on first use.
+const LANGUAGES: &[(&str, &str)] = &[
+    ("rs", "rust"),
+    ("py", "python"),
+    ("pyi", "python"),
+    ("ts", "typescript"),
+    ("tsx", "tsx"),
+    ("js", "javascript"),
+    ("mjs", "javascript"),
+    ("cjs", "javascript"),
+    ("jsx", "jsx"),
+    ("go", "go"),
+    ("rb", "ruby"),
+    ("java", "java"),
+    ("kt", "kotlin"),
+    ("kts", "kotlin"),
+    ("scala", "scala"),
+    ("groovy", "groovy"),
+    ("c", "c"),
+    ("h", "c"),
+    ("cc", "cpp"),
+    ("cpp", "cpp"),
+    ("cxx", "cpp"),
+    ("hpp", "cpp"),
+    ("hh", "cpp"),
+    ("cs", "csharp"),
+    ("fs", "fsharp"),
+    ("swift", "swift"),
+    ("m", "objectivec"),
+    ("mm", "objectivec"),
+    ("php", "php"),
+    ("pl", "perl"),
+    ("pm", "perl"),
+    ("lua", "lua"),
+    ("r", "r"),
+    ("jl", "julia"),
+    ("dart", "dart"),
+    ("ex", "elixir"),
+    ("exs", "elixir"),
+    ("erl", "erlang"),
+    ("hs", "haskell"),
+    ("ml", "ocaml"),
+    ("mli", "ocaml"),
+    ("clj", "clojure"),
+    ("cljs", "clojure"),
+    ("elm", "elm"),
+    ("zig", "zig"),
+    ("nim", "nim"),
+    ("v", "v"),
+    ("sol", "solidity"),
+    ("sh", "bash"),
+    ("bash", "bash"),
+    ("zsh", "zsh"),
+    ("fish", "fish"),
+    ("ps1", "powershell"),
+    ("bat", "batch"),
+    ("sql", "sql"),
+    ("html", "html"),
+    ("htm", "html"),
+    ("xml", "xml"),
+    ("svg", "xml"),
+    ("css", "css"),
+    ("scss", "scss"),
+    ("sass", "sass"),
+    ("less", "less"),
+    ("vue", "vue"),
+    ("svelte", "svelte"),
+    ("json", "json"),
+    ("jsonc", "jsonc"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+    ("toml", "toml"),
+    ("ini", "ini"),
+    ("md", "markdown"),
+    ("markdown", "markdown"),
+    ("tex", "latex"),
+    ("proto", "protobuf"),
+    ("graphql", "graphql"),
+    ("gql", "graphql"),
+    ("tf", "hcl"),
+    ("hcl", "hcl"),
+    ("nix", "nix"),
+    ("dockerfile", "dockerfile"),
+    ("mk", "makefile"),
+    ("cmake", "cmake"),
+    ("diff", "diff"),
+    ("patch", "diff"),
+];
+
+static LANGUAGE_MAP: LazyLock<HashMap<&'static str, &'static str>> =
+    LazyLock::new(|| LANGUAGES.iter().copied().collect());
+
+/// Returns the language for a file extension (without the leading dot), if known.
+///
+/// The lookup is case-insensitive, so `RS` and `rs` both map to `rust`.
+pub fn language_for_extension(ext: &str) -> Option<&'static str> {
+    LANGUAGE_MAP.get(ext.to_ascii_lowercase().as_str()).copied()
+}
+
+/// Returns the language of a file by its extension (or full name, e.g. `Makefile`), if known.
+pub fn language_for_path(path: &Path) -> Option<&'static str> {
+    path.extension()
+        .or_else(|| path.file_name())
+        .and_then(|name| language_for_extension(&name.to_string_lossy()))
+}
+
+#[cfg(test)]
+mod lang_tests {
+    use super::*;
+
+    #[test]
+    fn test_language_for_extension() {
+        assert_eq!(language_for_extension("rs"), Some("rust"));
+        assert_eq!(language_for_extension("py"), Some("python"));
+        assert_eq!(language_for_extension("ts"), Some("typescript"));
+        assert_eq!(language_for_extension("go"), Some("go"));
+        assert_eq!(language_for_extension("rb"), Some("ruby"));
+        assert_eq!(language_for_extension("RS"), Some("rust"));
+        assert_eq!(language_for_extension("unknownext"), None);
+    }
+
+    #[test]
+    fn test_language_for_path() {
+        assert_eq!(language_for_path(Path::new("src/main.rs")), Some("rust"));
+        assert_eq!(language_for_path(Path::new("notes.unknownext")), None);
+    }
+
+    #[test]
+    fn test_language_map_has_no_duplicate_extensions() {
+        assert_eq!(LANGUAGE_MAP.len(), LANGUAGES.len());
+    }
+}
@@ -1,7 +1,12 @@
 pub mod clipboard;
+pub mod config;
 pub mod editor;
+pub mod editorconfig;
 pub mod errors;
-mod exclude;
+pub mod exclude;
+pub mod git;
+pub mod lang;
+pub mod secrets;
 pub mod traversal;
 pub mod ui;
 pub mod utils;
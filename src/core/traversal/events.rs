@@ -0,0 +1,69 @@
+//! events - Streams machine-readable progress events while files are bundled.
+//!
+//! With `--events ndjson`, one JSON object per line is written as each file lands in
+//! the bundle, then a final summary, so integrations can follow a run in real time:
+//!
+//! ```text
+//! {"event":"file","path":"src/main.rs","bytes":120}
+//! {"event":"done","count":1}
+//! ```
+//!
+//! Events never go to stdout, which may be carrying the bundle itself.
+
+use crate::core::errors::FileSystemError;
+use anyhow::Context;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Destination of the event stream; shared by every walker of a run.
+pub struct EventSink {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl EventSink {
+    /// Streams events to stderr.
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+
+    /// Streams events to a fresh file at `path` (e.g. `/dev/fd/3` for a separate descriptor).
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WriteFailed` if the file cannot be created.
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        let file = File::create(path)
+            .map_err(|e| FileSystemError::WriteFailed {
+                path: path.to_path_buf(),
+                source: e,
+            })
+            .with_context(|| format!("Failed to open events output: {}", path.display()))?;
+        Ok(Self::new(file))
+    }
+
+    /// Reports a file written to the bundle, with its path relative to the root.
+    pub fn file(&self, path: &str, bytes: u64) {
+        self.emit(serde_json::json!({ "event": "file", "path": path, "bytes": bytes }));
+    }
+
+    /// Reports the end of the run and how many files were bundled in total.
+    pub fn done(&self, count: usize) {
+        self.emit(serde_json::json!({ "event": "done", "count": count }));
+    }
+
+    fn new(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Mutex::new(Box::new(out)),
+        }
+    }
+
+    /// Writes one event line, ignoring write failures: like decorative output,
+    /// a consumer that went away must never abort the bundle.
+    fn emit(&self, event: serde_json::Value) {
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(out, "{event}");
+        let _ = out.flush();
+    }
+}
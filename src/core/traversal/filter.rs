@@ -1,5 +1,21 @@
 //! filter - Provides filtering functions for directory traversal operations.
 
+use crate::core::traversal::limiter::OpenFileLimiter;
+use crate::core::ui::console;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Maximum number of bytes scanned when checking a file's line lengths.
+///
+/// Minified bundles give themselves away early, so there's no need to read huge files in full.
+const LINE_SCAN_LIMIT_BYTES: u64 = 1024 * 1024;
+
+/// Number of leading bytes searched for NUL bytes when sniffing for binary content.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
 /// Checks if a directory entry is hidden (starts with a dot).
 ///
 /// # Arguments
@@ -17,13 +33,145 @@ pub fn is_hidden(entry: &walkdir::DirEntry, verbose: bool) -> bool {
         .map(|str| {
             let hidden_entry = str.starts_with('.');
             if hidden_entry && verbose {
-                println!("Hidden entry '{}' was skipped", entry.path().display());
+                console::line(format!(
+                    "Hidden entry '{}' was skipped",
+                    entry.path().display()
+                ));
             }
             hidden_entry
         })
         .unwrap_or(false)
 }
 
+/// Checks if a file name ends in one of `extensions` (lowercase, without the dot).
+///
+/// Every dotted suffix counts, so `tar.gz` matches `a.tar.gz`; a leading dot doesn't
+/// start an extension, so `.env` has none.
+pub fn has_extension_in(path: &Path, extensions: &HashSet<String>) -> bool {
+    if extensions.is_empty() {
+        return false;
+    }
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let name = name.to_lowercase();
+    name.match_indices('.')
+        .filter(|&(index, _)| index > 0)
+        .any(|(index, _)| extensions.contains(&name[index + 1..]))
+}
+
+/// Checks if a file contains a line longer than `max_line_length` characters.
+///
+/// Only the first [`LINE_SCAN_LIMIT_BYTES`] of the file are scanned, in fixed-size chunks,
+/// so even a huge single-line file is never loaded into memory at once. The file is held
+/// open under a `limiter` permit.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn has_long_line(
+    limiter: &OpenFileLimiter,
+    path: &Path,
+    max_line_length: usize,
+) -> io::Result<bool> {
+    let _permit = limiter.acquire();
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file.take(LINE_SCAN_LIMIT_BYTES));
+    let mut line_length = 0;
+
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            return Ok(false);
+        }
+
+        for &byte in chunk {
+            if byte == b'\n' {
+                line_length = 0;
+            } else if byte & 0xC0 != 0x80 {
+                // Count UTF-8 characters, not bytes: skip continuation bytes
+                line_length += 1;
+                if line_length > max_line_length {
+                    return Ok(true);
+                }
+            }
+        }
+
+        let consumed = chunk.len();
+        reader.consume(consumed);
+    }
+}
+
+/// Checks if a file has more than `max_lines` lines.
+///
+/// Newlines are counted in fixed-size chunks and the scan stops as soon as the cap is
+/// exceeded, so a multi-million-line log is never read past its first `max_lines` lines.
+/// A final line without a trailing newline still counts. The file is held open under
+/// a `limiter` permit.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read.
+pub fn exceeds_line_count(
+    limiter: &OpenFileLimiter,
+    path: &Path,
+    max_lines: usize,
+) -> io::Result<bool> {
+    let _permit = limiter.acquire();
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file);
+    let mut lines = 0;
+    let mut last_byte = b'\n';
+
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            // An unterminated last line counts too
+            if last_byte != b'\n' {
+                lines += 1;
+            }
+            return Ok(lines > max_lines);
+        }
+
+        lines += chunk.iter().filter(|&&byte| byte == b'\n').count();
+        if lines > max_lines {
+            return Ok(true);
+        }
+
+        last_byte = chunk[chunk.len() - 1];
+        let consumed = chunk.len();
+        reader.consume(consumed);
+    }
+}
+
+/// Checks whether a text file's content matches `pattern` anywhere.
+///
+/// Binary and non-UTF-8 files never match, since they aren't bundled as text. The file
+/// is read under a `limiter` permit.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn content_matches(
+    limiter: &OpenFileLimiter,
+    path: &Path,
+    pattern: &Regex,
+) -> io::Result<bool> {
+    let bytes = limiter.read(path)?;
+    if looks_binary(&bytes) {
+        return Ok(false);
+    }
+    Ok(std::str::from_utf8(&bytes).is_ok_and(|text| pattern.is_match(text)))
+}
+
+/// Checks whether file content looks binary: a NUL byte within the first few KB.
+///
+/// Text encodings the bundle can hold (UTF-8, ASCII) never contain NUL, while most
+/// binary formats (and UTF-16 text) do, early on.
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
 #[cfg(test)]
 mod filter_tests {
     use super::*;
@@ -103,4 +251,102 @@ mod filter_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_is_hidden_quiet_unless_verbose() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(temp_dir.path().join(".hidden"), "")?;
+        fs::write(temp_dir.path().join("visible.txt"), "")?;
+
+        let entries: Vec<_> = WalkDir::new(temp_dir.path())
+            .min_depth(1)
+            .sort_by_file_name()
+            .into_iter()
+            .collect::<Result<_, _>>()?;
+
+        let (hidden, captured) = console::capture::run(|| {
+            entries
+                .iter()
+                .map(|e| is_hidden(e, false))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(hidden, [true, false]);
+        assert!(captured.stdout.is_empty());
+
+        let (_, captured) = console::capture::run(|| is_hidden(&entries[0], true));
+        assert!(captured.stdout.contains("Hidden entry"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_long_line() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let minified = temp_dir.path().join("app.min.js");
+        let normal = temp_dir.path().join("app.js");
+        fs::write(&minified, "x".repeat(10_000))?;
+        fs::write(&normal, "let a = 1;\nlet b = 2;\n".repeat(1_000))?;
+
+        let limiter = OpenFileLimiter::new(1);
+        assert!(has_long_line(&limiter, &minified, 5_000)?);
+        assert!(!has_long_line(&limiter, &normal, 5_000)?);
+
+        // Multi-byte characters count once each
+        let unicode = temp_dir.path().join("unicode.txt");
+        fs::write(&unicode, "✨".repeat(10))?;
+        assert!(!has_long_line(&limiter, &unicode, 10)?);
+        assert!(has_long_line(&limiter, &unicode, 9)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exceeds_line_count() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let log = temp_dir.path().join("app.log");
+        fs::write(&log, "line\n".repeat(100))?;
+
+        let limiter = OpenFileLimiter::new(1);
+        assert!(exceeds_line_count(&limiter, &log, 99)?);
+        assert!(!exceeds_line_count(&limiter, &log, 100)?);
+
+        // An unterminated last line still counts
+        let unterminated = temp_dir.path().join("data.csv");
+        fs::write(&unterminated, "a\nb\nc")?;
+        assert!(exceeds_line_count(&limiter, &unterminated, 2)?);
+        assert!(!exceeds_line_count(&limiter, &unterminated, 3)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_looks_binary() {
+        assert!(looks_binary(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"));
+        assert!(!looks_binary("plain text ✨\n".as_bytes()));
+
+        // NUL bytes past the sniffed prefix aren't looked for
+        let mut late_nul = vec![b'a'; BINARY_SNIFF_BYTES];
+        late_nul.push(0);
+        assert!(!looks_binary(&late_nul));
+    }
+
+    #[test]
+    fn test_content_matches() -> io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let source = temp_dir.path().join("lib.rs");
+        let binary = temp_dir.path().join("blob.bin");
+        fs::write(&source, "fn parse_config() {}\n")?;
+        fs::write(&binary, b"parse_config\0\0")?;
+
+        let pattern = Regex::new(r"parse_\w+").unwrap();
+        let limiter = OpenFileLimiter::new(1);
+        assert!(content_matches(&limiter, &source, &pattern)?);
+        assert!(!content_matches(
+            &limiter,
+            &source,
+            &Regex::new("TODO").unwrap()
+        )?);
+        assert!(!content_matches(&limiter, &binary, &pattern)?);
+        Ok(())
+    }
 }
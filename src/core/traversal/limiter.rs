@@ -0,0 +1,108 @@
+//! limiter - Bounds how many files are open for reading at the same time.
+//!
+//! Systems with a low `ulimit -n` can run out of file descriptors when many files
+//! are read concurrently. Every read goes through an [`OpenFileLimiter`], which acts
+//! as a counting semaphore: at most `max_open` permits exist at once.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Condvar, Mutex};
+
+/// Default number of files that may be open simultaneously (`--max-open-files`).
+pub const DEFAULT_MAX_OPEN_FILES: usize = 64;
+
+/// Counting semaphore guarding file-open operations.
+pub struct OpenFileLimiter {
+    max_open: usize,
+    open: Mutex<usize>,
+    released: Condvar,
+}
+
+/// Permission to keep one file open; released when dropped.
+pub struct OpenFilePermit<'a> {
+    limiter: &'a OpenFileLimiter,
+}
+
+impl OpenFileLimiter {
+    /// Creates a limiter allowing up to `max_open` open files (at least one).
+    pub fn new(max_open: usize) -> Self {
+        Self {
+            max_open: max_open.max(1),
+            open: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Blocks until a file may be opened and returns the permit for it.
+    pub fn acquire(&self) -> OpenFilePermit<'_> {
+        let mut open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+        while *open >= self.max_open {
+            open = self.released.wait(open).unwrap_or_else(|e| e.into_inner());
+        }
+        *open += 1;
+        OpenFilePermit { limiter: self }
+    }
+
+    /// Reads a whole file's bytes while holding a permit.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the file cannot be opened or read.
+    pub fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let _permit = self.acquire();
+        fs::read(path)
+    }
+}
+
+impl Drop for OpenFilePermit<'_> {
+    fn drop(&mut self) {
+        let mut open = self.limiter.open.lock().unwrap_or_else(|e| e.into_inner());
+        *open -= 1;
+        self.limiter.released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod limiter_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_open_count_never_exceeds_limit() {
+        let limiter = OpenFileLimiter::new(3);
+        let open_now = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        // Instrumented "opener": tracks how many permits are held at once
+        thread::scope(|scope| {
+            for _ in 0..16 {
+                scope.spawn(|| {
+                    let _permit = limiter.acquire();
+                    let current = open_now.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(current, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(5));
+                    open_now.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert_eq!(open_now.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_zero_limit_still_allows_one_file() -> anyhow::Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let file = temp_dir.path().join("a.txt");
+        fs::write(&file, "content")?;
+
+        let limiter = OpenFileLimiter::new(0);
+        assert_eq!(limiter.read(&file)?, b"content");
+        assert_eq!(limiter.read(&file)?, b"content");
+
+        Ok(())
+    }
+}
@@ -1,2 +1,5 @@
+pub mod events;
 mod filter;
+pub mod limiter;
+pub mod sink;
 pub mod walker;
@@ -0,0 +1,79 @@
+//! sink - A writer the bundle can stream into instead of the output file, e.g. stdout.
+//!
+//! Every walker of a run writes into the same sink, one input after another, so the sink
+//! keeps count of the bytes it received: section separators and `--index-out` offsets
+//! continue where the previous input stopped, just as they do when appending to a file.
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+/// A shared, cloneable destination for the bundle.
+#[derive(Clone)]
+pub struct BundleSink {
+    inner: Arc<Mutex<SinkState>>,
+}
+
+struct SinkState {
+    out: Box<dyn Write + Send>,
+    written: u64,
+}
+
+impl BundleSink {
+    /// Wraps `out` (e.g. `io::stdout()`) as a bundle sink.
+    pub fn new(out: impl Write + Send + 'static) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SinkState {
+                out: Box::new(out),
+                written: 0,
+            })),
+        }
+    }
+
+    /// Creates a sink collecting everything in memory; returns it with the shared buffer.
+    #[cfg(test)]
+    pub fn in_memory() -> (Self, Arc<Mutex<Vec<u8>>>) {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        (Self::new(SharedBuffer(Arc::clone(&buffer))), buffer)
+    }
+
+    /// Bytes written to the sink so far, i.e. the offset the next write lands at.
+    pub fn written(&self) -> u64 {
+        self.lock().written
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SinkState> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Write for BundleSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.lock();
+        let written = state.out.write(buf)?;
+        state.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.lock().out.flush()
+    }
+}
+
+/// The writer behind [`BundleSink::in_memory`].
+#[cfg(test)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+#[cfg(test)]
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 //! animations - Provides terminal animation utilities for visual feedback.
 
+use crate::core::ui::console;
 use colored::Colorize;
-use std::io::{stdout, Write};
 use std::{thread, time};
 
 /// Spinner provides animated loading indicators with customizable frames and colors.
@@ -48,22 +48,21 @@ impl Spinner {
 
         for (i, frame) in self.frames.iter().enumerate() {
             let color = &self.colors[i % self.colors.len()];
-            print!(
+            console::inline(format!(
                 "\r{} {} {}",
                 frame.color(*color),
                 message.bright_cyan(),
                 "...".dimmed()
-            );
-            stdout().flush().unwrap();
+            ));
             thread::sleep(time::Duration::from_millis(frame_duration));
         }
 
-        println!(
+        console::line(format!(
             "\r{} {} {}",
             "✓".bright_green(),
             message.bright_green(),
             "Done!".dimmed()
-        );
+        ));
     }
 }
 
@@ -75,13 +74,12 @@ impl Spinner {
 /// * `count` - Number of dots to animate
 /// * `delay_ms` - Delay between each dot in milliseconds
 pub fn animated_dots(text: &str, count: usize, delay_ms: u64) {
-    print!("{}", text.bright_yellow());
+    console::inline(text.bright_yellow());
     for _ in 0..count {
-        print!("{}", ".".bright_yellow());
-        stdout().flush().unwrap();
+        console::inline(".".bright_yellow());
         thread::sleep(time::Duration::from_millis(delay_ms));
     }
-    println!();
+    console::line("");
 }
 
 /// Generates a progress counter message at specified intervals.
@@ -96,7 +94,7 @@ pub fn animated_dots(text: &str, count: usize, delay_ms: u64) {
 ///
 /// Returns `Some(message)` if counter should be displayed, `None` otherwise.
 pub fn progress_counter(emoji_set: &[&str], current: usize, interval: usize) -> Option<String> {
-    if current % interval == 0 {
+    if current.is_multiple_of(interval) {
         let idx = (current / interval) % emoji_set.len();
         Some(format!(
             "{} Collected {} files so far...",
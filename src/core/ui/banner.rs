@@ -1,8 +1,10 @@
 //! banner - Provides welcome and goodbye banner displays for the application.
 
+use crate::core::ui::console;
 use crate::core::ui::table::{Align, BorderStyle, FormattedBox};
 use colored::Colorize;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::sync::LazyLock;
 
 // -------------------------------------------- Constants --------------------------------------------
@@ -73,32 +75,40 @@ const KAOMOJIS: &[&str] = &[
     "(ﾉ^ヮ^)ﾉ*:・ﾟ✧",
 ];
 
+/// Creates the RNG behind all decorations, seeded from `seed` when one is given.
+///
+/// The same seed always yields the same banner, goodbye message, and kaomoji,
+/// which keeps terminal captures reproducible.
+pub fn decoration_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_rng(&mut rand::rng()),
+    }
+}
+
 /// Displays a randomly selected welcome banner.
-pub fn print_welcome() {
-    let mut rng = rand::rng();
+pub fn print_welcome(rng: &mut impl Rng) {
     let banner = &BANNERS[rng.random_range(0..BANNERS.len())];
-    println!("{}", banner.bright_magenta());
+    console::line(banner.bright_magenta());
 }
 
 /// Displays a goodbye message with a random kaomoji.
-pub fn print_goodbye() {
-    println!("\n{}", "━".repeat(55).bright_cyan());
+pub fn print_goodbye(rng: &mut impl Rng) {
+    console::line(format!("\n{}", "━".repeat(55).bright_cyan()));
 
-    let mut rng = rand::rng();
     let message = GOODBYE_MESSAGES[rng.random_range(0..GOODBYE_MESSAGES.len())];
 
-    println!("    {}", message.bright_green().bold());
-    println!(
+    console::line(format!("    {}", message.bright_green().bold()));
+    console::line(format!(
         "    {} {}",
-        get_random_kaomoji(),
+        get_random_kaomoji(rng),
         "Have a wonderful day!".bright_yellow()
-    );
-    println!("{}\n", "━".repeat(55).bright_cyan());
+    ));
+    console::line(format!("{}\n", "━".repeat(55).bright_cyan()));
 }
 
 /// Returns a random kaomoji from the collection.
-pub fn get_random_kaomoji() -> &'static str {
-    let mut rng = rand::rng();
+pub fn get_random_kaomoji(rng: &mut impl Rng) -> &'static str {
     KAOMOJIS[rng.random_range(0..KAOMOJIS.len())]
 }
 
@@ -133,16 +143,32 @@ mod banner_tests {
 
     #[test]
     fn test_get_random_kaomoji_returns_valid() {
-        let kaomoji = get_random_kaomoji();
+        let kaomoji = get_random_kaomoji(&mut decoration_rng(None));
         assert!(KAOMOJIS.contains(&kaomoji));
     }
 
     #[test]
     fn test_get_random_kaomoji_multiple_calls() {
         // Test that function can be called multiple times
+        let mut rng = decoration_rng(None);
         for _ in 0..10 {
-            let kaomoji = get_random_kaomoji();
+            let kaomoji = get_random_kaomoji(&mut rng);
             assert!(KAOMOJIS.contains(&kaomoji));
         }
     }
+
+    #[test]
+    fn test_same_seed_renders_identically() {
+        let render = |seed| {
+            let mut rng = decoration_rng(Some(seed));
+            let ((), captured) = console::capture::run(|| {
+                print_welcome(&mut rng);
+                print_goodbye(&mut rng);
+            });
+            captured.stdout
+        };
+
+        assert_eq!(render(42), render(42));
+        assert_eq!(render(7), render(7));
+    }
 }
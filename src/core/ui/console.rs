@@ -0,0 +1,191 @@
+//! console - Routes decorative terminal output to stdout or stderr.
+//!
+//! Banners, spinners, and log lines normally go to stdout. When the bundle itself
+//! is written to stdout (`--stdout`), everything decorative is routed to stderr
+//! instead so `treeclip run . --stdout > bundle.txt` yields a clean file.
+//! With `--quiet` none of it is printed at all.
+
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether decorative output currently goes to stderr.
+static ROUTE_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Whether decorative output and warnings are suppressed.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Routes all decorative output to stderr (`true`) or stdout (`false`).
+pub fn route_to_stderr(enabled: bool) {
+    ROUTE_TO_STDERR.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if decorative output is currently routed to stderr.
+pub fn is_routed_to_stderr() -> bool {
+    ROUTE_TO_STDERR.load(Ordering::Relaxed)
+}
+
+/// Suppresses all decorative output and warnings (`true`); errors are still reported.
+pub fn set_quiet(enabled: bool) {
+    QUIET.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if decorative output is currently suppressed.
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Prints a warning line to stderr, whichever stream decorative output goes to.
+pub fn warning(message: impl Display) {
+    emit_to(true, format_args!("{message}\n"));
+}
+
+/// Prints a line of decorative output to the active stream.
+pub fn line(message: impl Display) {
+    emit(format_args!("{message}\n"));
+}
+
+/// Prints decorative output without a trailing newline and flushes immediately.
+///
+/// Used by animations that redraw the current line with `\r`.
+pub fn inline(message: impl Display) {
+    emit(format_args!("{message}"));
+}
+
+/// Prints a progress update to stderr without a trailing newline and flushes immediately.
+///
+/// Progress redraws its line with `\r`, which would only garble redirected stdout.
+pub fn progress(message: impl Display) {
+    emit_to(true, format_args!("{message}"));
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Writes the formatted output to the active stream.
+fn emit(args: fmt::Arguments) {
+    emit_to(is_routed_to_stderr(), args);
+}
+
+/// Writes the formatted output to stderr or stdout unless quiet, ignoring write failures
+/// (decorative output must never abort a run).
+fn emit_to(to_stderr: bool, args: fmt::Arguments) {
+    if is_quiet() {
+        return;
+    }
+
+    #[cfg(test)]
+    if capture::record(to_stderr, &args) {
+        return;
+    }
+
+    if to_stderr {
+        let mut stderr = io::stderr().lock();
+        let _ = stderr.write_fmt(args);
+        let _ = stderr.flush();
+    } else {
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_fmt(args);
+        let _ = stdout.flush();
+    }
+}
+
+/// Thread-local capture of console output for tests.
+#[cfg(test)]
+pub mod capture {
+    use std::cell::RefCell;
+    use std::fmt;
+    use std::sync::Mutex;
+
+    /// Console output captured while running a closure.
+    #[derive(Debug, Default)]
+    pub struct Captured {
+        pub stdout: String,
+        pub stderr: String,
+    }
+
+    thread_local! {
+        static BUFFER: RefCell<Option<Captured>> = const { RefCell::new(None) };
+    }
+
+    /// Serializes captures, since the routing and quiet flags are process-wide.
+    static ROUTING_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Runs `f`, capturing everything printed through the console on this thread.
+    ///
+    /// Routing is reset to stdout, and quiet mode off, before and after `f` runs.
+    pub fn run<T>(f: impl FnOnce() -> T) -> (T, Captured) {
+        let _guard = ROUTING_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        super::route_to_stderr(false);
+        super::set_quiet(false);
+        BUFFER.with(|buffer| *buffer.borrow_mut() = Some(Captured::default()));
+        let result = f();
+        super::route_to_stderr(false);
+        super::set_quiet(false);
+        let captured = BUFFER.with(|buffer| buffer.borrow_mut().take().unwrap_or_default());
+        (result, captured)
+    }
+
+    /// Records output if a capture is active on this thread; returns whether it was recorded.
+    pub(super) fn record(to_stderr: bool, args: &fmt::Arguments) -> bool {
+        BUFFER.with(|buffer| match buffer.borrow_mut().as_mut() {
+            Some(captured) => {
+                let target = if to_stderr {
+                    &mut captured.stderr
+                } else {
+                    &mut captured.stdout
+                };
+                fmt::write(target, *args).is_ok()
+            }
+            None => false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod console_tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_records_lines_and_inline_output() {
+        let ((), captured) = capture::run(|| {
+            inline("\rloading");
+            line("done");
+        });
+
+        assert_eq!(captured.stdout, "\rloadingdone\n");
+        assert!(captured.stderr.is_empty());
+    }
+
+    #[test]
+    fn test_capture_follows_stderr_routing() {
+        let ((), captured) = capture::run(|| {
+            route_to_stderr(true);
+            line("progress");
+        });
+
+        assert!(captured.stdout.is_empty());
+        assert_eq!(captured.stderr, "progress\n");
+        assert!(!is_routed_to_stderr());
+    }
+
+    #[test]
+    fn test_quiet_suppresses_lines_and_warnings() {
+        let ((), captured) = capture::run(|| {
+            warning("careful");
+            set_quiet(true);
+            line("progress");
+            warning("ignored");
+        });
+
+        assert!(captured.stdout.is_empty());
+        assert_eq!(captured.stderr, "careful\n");
+        assert!(!is_quiet());
+    }
+
+    #[test]
+    fn test_output_outside_capture_is_not_recorded() {
+        let ((), captured) = capture::run(|| {});
+        assert!(captured.stdout.is_empty());
+        assert!(captured.stderr.is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 //! formatter - Provides formatting utilities for configuration display and statistics.
 
+use crate::commands::args::WordMode;
+use crate::core::lang;
 use crate::core::ui::table::FormattedBox;
 use crate::core::utils;
 use colored::{ColoredString, Colorize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 const LABEL_WIDTH: usize = 18;
@@ -57,24 +60,43 @@ impl ConfigFormatter {
 }
 
 /// StatsBox displays content statistics in a formatted box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct StatsBox {
     lines: usize,
     chars: usize,
     words: usize,
+    /// Estimated LLM tokens (see [`utils::estimate_tokens`]).
+    tokens: usize,
     bytes: usize,
 }
 
 impl StatsBox {
     /// Creates a new StatsBox with the specified statistics.
-    pub fn new(lines: usize, chars: usize, words: usize, bytes: usize) -> Self {
+    pub fn new(lines: usize, chars: usize, words: usize, tokens: usize, bytes: usize) -> Self {
         Self {
             lines,
             chars,
             words,
+            tokens,
             bytes,
         }
     }
 
+    /// Computes the statistics for a piece of content, counting words per `word_mode`.
+    pub fn from_content(content: &str, word_mode: WordMode) -> Self {
+        let words = content
+            .split(|ch| !word_mode.is_word_char(ch))
+            .filter(|word| !word.is_empty())
+            .count();
+        Self::new(
+            content.split('\n').count(),
+            content.chars().count(),
+            words,
+            utils::estimate_tokens(content),
+            content.len(),
+        )
+    }
+
     /// Renders the statistics box as a formatted string.
     pub fn render(&self) -> String {
         FormattedBox::new("Content Statistics")
@@ -96,6 +118,12 @@ impl StatsBox {
                     .bright_white()
                     .to_string(),
             )
+            .row(
+                "🔤 Tokens:",
+                format!("~{}", utils::format_number(self.tokens as i64))
+                    .bright_white()
+                    .to_string(),
+            )
             .row(
                 "💾 Size:",
                 utils::format_bytes(self.bytes).bright_white().to_string(),
@@ -103,6 +131,23 @@ impl StatsBox {
             .render()
     }
 
+    /// Renders the statistics as plain text without colors or box drawing, e.g. for the clipboard.
+    pub fn render_plain(&self) -> String {
+        format!(
+            "Content Statistics\n\
+             Characters: {}\n\
+             Lines: {}\n\
+             Words: {}\n\
+             Tokens: ~{}\n\
+             Size: {}\n",
+            utils::format_number(self.chars as i64),
+            utils::format_number(self.lines as i64),
+            utils::format_number(self.words as i64),
+            utils::format_number(self.tokens as i64),
+            utils::format_bytes(self.bytes),
+        )
+    }
+
     /// Returns an emoji and message based on file size.
     pub fn get_size_message(&self) -> (String, String) {
         match self.bytes {
@@ -128,6 +173,280 @@ impl StatsBox {
     }
 }
 
+/// Accumulates [`StatsBox`] counts chunk by chunk, as content is written.
+///
+/// Pushing chunks gives the same counts as [`StatsBox::from_content`] on their
+/// concatenation, so `--stats` doesn't need to re-read the bundle.
+///
+/// Words are tallied in every [`WordMode`] at once, so the mode can be picked afterwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatsTally {
+    newlines: usize,
+    chars: usize,
+    bytes: usize,
+    whitespace_words: WordTally,
+    alnum_words: WordTally,
+}
+
+/// Word count for one [`WordMode`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct WordTally {
+    words: usize,
+    /// Whether the last chunk ended mid-word, so the next chunk may continue that word.
+    in_word: bool,
+}
+
+impl StatsTally {
+    /// Counts a chunk of content written after everything pushed so far.
+    pub fn push(&mut self, chunk: &str) {
+        self.bytes += chunk.len();
+        for ch in chunk.chars() {
+            self.chars += 1;
+            if ch == '\n' {
+                self.newlines += 1;
+            }
+            self.whitespace_words
+                .push(WordMode::Whitespace.is_word_char(ch));
+            self.alnum_words.push(WordMode::Alnum.is_word_char(ch));
+        }
+    }
+
+    /// Appends a tally of content written after this one's.
+    ///
+    /// Words are assumed not to straddle the join, which holds for bundle sections
+    /// since each one ends with a newline.
+    pub fn merge(&mut self, other: StatsTally) {
+        self.newlines += other.newlines;
+        self.chars += other.chars;
+        self.bytes += other.bytes;
+        self.whitespace_words
+            .merge(other.whitespace_words, other.bytes);
+        self.alnum_words.merge(other.alnum_words, other.bytes);
+    }
+
+    /// Returns the number of complete lines tallied, i.e. the newlines.
+    pub fn newlines(&self) -> usize {
+        self.newlines
+    }
+
+    /// Returns the statistics of everything tallied, counting words per `word_mode`.
+    pub fn stats(&self, word_mode: WordMode) -> StatsBox {
+        let words = match word_mode {
+            WordMode::Whitespace => self.whitespace_words.words,
+            WordMode::Alnum => self.alnum_words.words,
+        };
+        let tokens = utils::token_estimate(self.chars, self.whitespace_words.words);
+        StatsBox::new(self.newlines + 1, self.chars, words, tokens, self.bytes)
+    }
+}
+
+impl WordTally {
+    fn push(&mut self, is_word_char: bool) {
+        if is_word_char && !self.in_word {
+            self.words += 1;
+        }
+        self.in_word = is_word_char;
+    }
+
+    fn merge(&mut self, other: WordTally, other_bytes: usize) {
+        self.words += other.words;
+        if other_bytes > 0 {
+            self.in_word = other.in_word;
+        }
+    }
+}
+
+/// Buckets of the file size histogram: label and exclusive upper bound in bytes.
+const SIZE_BUCKETS: [(&str, u64); 4] = [
+    ("< 1 KB", 1024),
+    ("1-10 KB", 10 * 1024),
+    ("10-100 KB", 100 * 1024),
+    ("> 100 KB", u64::MAX),
+];
+
+/// Width of the longest histogram bar, in characters.
+const HISTOGRAM_BAR_WIDTH: usize = 30;
+
+/// SizeHistogram shows how the bundled files are distributed across size ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeHistogram {
+    counts: [usize; SIZE_BUCKETS.len()],
+}
+
+impl SizeHistogram {
+    /// Sorts file sizes (in bytes) into the histogram buckets.
+    pub fn from_sizes(sizes: &[u64]) -> Self {
+        let mut counts = [0; SIZE_BUCKETS.len()];
+        for &size in sizes {
+            let bucket = SIZE_BUCKETS
+                .iter()
+                .position(|&(_, limit)| size < limit)
+                .unwrap_or(SIZE_BUCKETS.len() - 1);
+            counts[bucket] += 1;
+        }
+        Self { counts }
+    }
+
+    /// Returns the number of files per bucket, smallest bucket first.
+    #[cfg(test)]
+    pub fn counts(&self) -> [usize; SIZE_BUCKETS.len()] {
+        self.counts
+    }
+
+    /// Renders one line per bucket with a bar scaled to the largest bucket.
+    pub fn render(&self) -> String {
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        SIZE_BUCKETS
+            .iter()
+            .zip(self.counts)
+            .map(|(&(label, _), count)| {
+                let bar = (count * HISTOGRAM_BAR_WIDTH).checked_div(max).unwrap_or(0);
+                // Any non-empty bucket gets at least one block
+                let bar = if count > 0 { bar.max(1) } else { 0 };
+                format!(
+                    "  {:<10} {} {}",
+                    label.bright_white(),
+                    "█".repeat(bar).bright_green(),
+                    count.to_string().dimmed()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Group for files whose extension isn't in the language map.
+const OTHER_LANGUAGE: &str = "other";
+
+/// Group for files without an extension, when grouping by extension.
+const NO_EXTENSION: &str = "(none)";
+
+/// How a [`LanguageReport`] groups the bundled files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LanguageGrouping {
+    /// By their lowercased extension (`--stats-by-ext`, collected while traversing);
+    /// most lines first.
+    #[default]
+    Extension,
+    /// By the language of their extension (`--language-report`); most files first.
+    Language,
+}
+
+/// LanguageReport tallies the bundled files, lines, and bytes per language or extension.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageReport {
+    grouping: LanguageGrouping,
+    groups: BTreeMap<String, LanguageTally>,
+}
+
+/// What a [`LanguageReport`] counted for one group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LanguageTally {
+    pub files: usize,
+    /// Lines of bundled content.
+    pub lines: usize,
+    /// Size on disk, in bytes.
+    pub bytes: u64,
+}
+
+impl LanguageReport {
+    /// Creates an empty report grouping files by `grouping`.
+    pub fn new(grouping: LanguageGrouping) -> Self {
+        Self {
+            grouping,
+            groups: BTreeMap::new(),
+        }
+    }
+
+    /// Tallies files by the language of their extension; unknown ones count as `other`.
+    pub fn from_paths<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Self {
+        let mut report = Self::new(LanguageGrouping::Language);
+        for path in paths {
+            report.add(path, 0, 0);
+        }
+        report
+    }
+
+    /// Counts one bundled file under its group.
+    pub fn add(&mut self, path: &Path, lines: usize, bytes: u64) {
+        let group = match self.grouping {
+            LanguageGrouping::Language => lang::language_for_path(path)
+                .unwrap_or(OTHER_LANGUAGE)
+                .to_string(),
+            LanguageGrouping::Extension => {
+                path.extension().map_or(NO_EXTENSION.to_string(), |ext| {
+                    ext.to_string_lossy().to_lowercase()
+                })
+            }
+        };
+        let tally = self.groups.entry(group).or_default();
+        tally.files += 1;
+        tally.lines += lines;
+        tally.bytes += bytes;
+    }
+
+    /// Adds another report of the same grouping (e.g. from the next input path) into this one.
+    pub fn merge(&mut self, other: LanguageReport) {
+        for (group, other) in other.groups {
+            let tally = self.groups.entry(group).or_default();
+            tally.files += other.files;
+            tally.lines += other.lines;
+            tally.bytes += other.bytes;
+        }
+    }
+
+    /// Returns the tally per group, most files (or lines, by extension) first; ties
+    /// alphabetically so the table is stable.
+    pub fn rows(&self) -> Vec<(&str, LanguageTally)> {
+        let mut rows: Vec<_> = self
+            .groups
+            .iter()
+            .map(|(group, tally)| (group.as_str(), *tally))
+            .collect();
+        rows.sort_by(|a, b| {
+            let (a_key, b_key) = match self.grouping {
+                LanguageGrouping::Language => (a.1.files, b.1.files),
+                LanguageGrouping::Extension => (a.1.lines, b.1.lines),
+            };
+            b_key.cmp(&a_key).then(a.0.cmp(b.0))
+        });
+        rows
+    }
+
+    /// Returns the number of files per group, in row order.
+    #[cfg(test)]
+    pub fn counts(&self) -> Vec<(&str, usize)> {
+        self.rows()
+            .into_iter()
+            .map(|(group, tally)| (group, tally.files))
+            .collect()
+    }
+
+    /// Renders the tally as a box with one row per group.
+    pub fn render(&self) -> String {
+        let title = match self.grouping {
+            LanguageGrouping::Language => "Languages",
+            LanguageGrouping::Extension => "By Extension",
+        };
+        self.rows()
+            .into_iter()
+            .fold(FormattedBox::new(title), |table, (group, tally)| {
+                let files = if tally.files == 1 { "file" } else { "files" };
+                let mut value = format!("{} {files}", utils::format_number(tally.files as i64));
+                if self.grouping == LanguageGrouping::Extension {
+                    let lines = if tally.lines == 1 { "line" } else { "lines" };
+                    value.push_str(&format!(
+                        ", {} {lines}, {}",
+                        utils::format_number(tally.lines as i64),
+                        utils::format_bytes(tally.bytes as usize)
+                    ));
+                }
+                table.row(format!("{group}:"), value.bright_white().to_string())
+            })
+            .render()
+    }
+}
+
 #[cfg(test)]
 mod formatter_tests {
     use super::*;
@@ -184,28 +503,65 @@ mod formatter_tests {
 
     #[test]
     fn test_stats_box_creation() {
-        let stats = StatsBox::new(100, 1000, 200, 5000);
+        let stats = StatsBox::new(100, 1000, 200, 260, 5000);
         assert_eq!(stats.lines, 100);
         assert_eq!(stats.chars, 1000);
         assert_eq!(stats.words, 200);
+        assert_eq!(stats.tokens, 260);
         assert_eq!(stats.bytes, 5000);
     }
 
+    #[test]
+    fn test_stats_tally_matches_from_content() {
+        let content = "=== Title ===\n==> src/main.rs\nfn main() {}\n\n==> ✨.md\n  two  words";
+        let mut tally = StatsTally::default();
+        // Chunk boundaries fall mid-word and mid-line
+        for chunk in [
+            "=== Ti",
+            "tle ===\n==> src/main.rs\nfn ma",
+            "in() {}\n\n",
+            "==> ✨.md\n  two  words",
+        ] {
+            tally.push(chunk);
+        }
+        for mode in [WordMode::Whitespace, WordMode::Alnum] {
+            assert_eq!(tally.stats(mode), StatsBox::from_content(content, mode));
+            assert_eq!(
+                StatsTally::default().stats(mode),
+                StatsBox::from_content("", mode)
+            );
+        }
+    }
+
+    #[test]
+    fn test_word_modes() {
+        let whitespace = StatsBox::from_content("a.b,c", WordMode::Whitespace);
+        let alnum = StatsBox::from_content("a.b,c", WordMode::Alnum);
+        assert_eq!(whitespace.words, 1);
+        assert_eq!(alnum.words, 3);
+
+        let mut tally = StatsTally::default();
+        tally.push("a.b,c");
+        assert_eq!(tally.stats(WordMode::Whitespace).words, 1);
+        assert_eq!(tally.stats(WordMode::Alnum).words, 3);
+    }
+
     #[test]
     fn test_stats_box_render() {
-        let stats = StatsBox::new(1, 100, 1_000, 1_000_000);
+        let stats = StatsBox::new(1, 100, 1_000, 0, 1_000_000);
         let rendered = stats.render();
 
         assert!(rendered.contains("Content Statistics"));
         assert!(rendered.contains("Characters:"));
         assert!(rendered.contains("Lines:"));
         assert!(rendered.contains("Words:"));
+        assert!(rendered.contains("Tokens:"));
         assert!(rendered.contains("Size:"));
     }
 
     #[test]
     fn test_get_size_message_tiny() {
-        let stats = StatsBox::new(1, 10, 2, 500);
+        let stats = StatsBox::new(1, 10, 2, 0, 500);
         let (emoji, message) = stats.get_size_message();
         assert_eq!(emoji, "🐣");
         assert!(message.contains("Tiny but mighty!"));
@@ -213,7 +569,7 @@ mod formatter_tests {
 
     #[test]
     fn test_get_size_message_small() {
-        let stats = StatsBox::new(10, 100, 20, 50_000);
+        let stats = StatsBox::new(10, 100, 20, 0, 50_000);
         let (emoji, message) = stats.get_size_message();
         assert_eq!(emoji, "🐇");
         assert!(message.contains("Perfect size!"));
@@ -221,7 +577,7 @@ mod formatter_tests {
 
     #[test]
     fn test_get_size_message_medium() {
-        let stats = StatsBox::new(100, 1000, 200, 500_000);
+        let stats = StatsBox::new(100, 1000, 200, 0, 500_000);
         let (emoji, message) = stats.get_size_message();
         assert_eq!(emoji, "🐘");
         assert!(message.contains("big one"));
@@ -229,9 +585,42 @@ mod formatter_tests {
 
     #[test]
     fn test_get_size_message_large() {
-        let stats = StatsBox::new(1000, 10000, 2000, 5_000_000);
+        let stats = StatsBox::new(1000, 10000, 2000, 0, 5_000_000);
         let (emoji, message) = stats.get_size_message();
         assert_eq!(emoji, "🐋");
         assert!(message.contains("whale"));
     }
+
+    #[test]
+    fn test_size_histogram_render() {
+        let histogram = SizeHistogram::from_sizes(&[10, 20, 5_000, 200_000]);
+        let rendered = histogram.render();
+        assert_eq!(rendered.lines().count(), 4);
+        assert!(rendered.contains("10-100 KB"));
+        assert!(rendered.contains(&"█".repeat(HISTOGRAM_BAR_WIDTH)));
+    }
+
+    #[test]
+    fn test_language_report_render() {
+        let paths = [Path::new("main.rs"), Path::new("README")];
+        let rendered = LanguageReport::from_paths(paths).render();
+        assert!(rendered.contains("Languages"));
+        assert!(rendered.contains("rust:"));
+        assert!(rendered.contains("other:"));
+        assert!(rendered.contains("1 file"));
+        assert!(!rendered.contains("1 files"));
+    }
+
+    #[test]
+    fn test_language_stats_render() {
+        let mut stats = LanguageReport::new(LanguageGrouping::Extension);
+        stats.add(Path::new("a.rs"), 12_000, 2048);
+        stats.add(Path::new("b.rs"), 1, 20);
+        stats.add(Path::new("Makefile"), 1, 40);
+        let rendered = stats.render();
+        assert!(rendered.contains("By Extension"));
+        assert!(rendered.contains("2 files, 12,001 lines, 2.0 KB"));
+        assert!(rendered.contains("1 file, 1 line, 40 B"));
+        assert!(rendered.contains("(none):"));
+    }
 }
@@ -0,0 +1,80 @@
+//! highlight - Syntax-highlights a text bundle with ANSI colors for terminal viewing.
+//!
+//! Only meant for output that lands directly on a terminal: the escape codes would
+//! corrupt a bundle written to a file or the clipboard.
+
+use colored::Colorize;
+use std::path::Path;
+use std::sync::LazyLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::{LinesWithEndings, as_24_bit_terminal_escaped};
+
+/// Theme used for highlighted content; readable on dark terminal backgrounds.
+const THEME_NAME: &str = "base16-ocean.dark";
+
+/// ANSI sequence that resets all colors, so nothing bleeds past a highlighted line.
+const RESET: &str = "\x1b[0m";
+
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    let mut themes = ThemeSet::load_defaults();
+    themes.themes.remove(THEME_NAME).unwrap_or_default()
+});
+
+/// Colorizes every file section of a text bundle according to its file's language.
+///
+/// Section headers (`==> path`) are made bold; anything before the first header
+/// (e.g. a `--title` block) and files of unknown languages are left as plain text.
+pub fn highlight_bundle(bundle: &str) -> String {
+    let mut highlighted = String::with_capacity(bundle.len() * 2);
+    let mut highlighter: Option<HighlightLines> = None;
+
+    for line in LinesWithEndings::from(bundle) {
+        if let Some(path) = line.strip_prefix("==> ") {
+            highlighted.push_str(&line.trim_end().bold().to_string());
+            highlighted.push('\n');
+            highlighter = syntax_for(Path::new(path.trim_end()))
+                .map(|syntax| HighlightLines::new(syntax, &THEME));
+            continue;
+        }
+
+        let escaped = highlighter.as_mut().and_then(|highlighter| {
+            let ranges = highlighter.highlight_line(line, &SYNTAXES).ok()?;
+            Some(as_24_bit_terminal_escaped(&ranges, false))
+        });
+        match escaped {
+            Some(escaped) => {
+                highlighted.push_str(&escaped);
+                highlighted.push_str(RESET);
+            }
+            None => highlighted.push_str(line),
+        }
+    }
+
+    highlighted
+}
+
+/// Finds the syntax for a file by its extension (or full name, e.g. `Makefile`).
+fn syntax_for(path: &Path) -> Option<&'static SyntaxReference> {
+    let token = path.extension().or_else(|| path.file_name())?.to_str()?;
+    SYNTAXES.find_syntax_by_extension(token)
+}
+
+#[cfg(test)]
+mod highlight_tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_bundle_colors_known_languages() {
+        let bundle = "==> src/main.rs\nfn main() {}\n\n==> notes.unknownext\nplain text\n";
+        let highlighted = highlight_bundle(bundle);
+
+        assert!(highlighted.contains("\x1b[38;2;"));
+        assert!(highlighted.contains("main"));
+        // Unknown languages pass through untouched
+        assert!(highlighted.contains("\nplain text\n"));
+    }
+}
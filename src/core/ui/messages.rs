@@ -36,6 +36,102 @@ impl Messages {
             .to_string()
     }
 
+    /// Returns the notice shown when a file is skipped for having an over-long line.
+    pub fn skipped_long_lines(path: &str, max_line_length: usize) -> String {
+        format!(
+            "{} {} {}",
+            "✂️".yellow(),
+            format!("Skipped {path}").yellow(),
+            format!("- has a line longer than {max_line_length} chars (minified?)").dimmed()
+        )
+    }
+
+    /// Returns the notice shown when a file is skipped for having too many lines.
+    pub fn skipped_too_many_lines(path: &str, max_lines: usize) -> String {
+        format!(
+            "{} {} {}",
+            "✂️".yellow(),
+            format!("Skipped {path}").yellow(),
+            format!("- has more than {max_lines} lines (log or data dump?)").dimmed()
+        )
+    }
+
+    /// Returns the notice shown when a git-backed flag (e.g. `--text-only-by-git`) is used
+    /// outside a git repository.
+    pub fn not_a_git_repo(path: &str, flag: &str) -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            format!("{path} is not inside a git repository").yellow(),
+            format!("- {flag} has no effect here").dimmed()
+        )
+    }
+
+    /// Returns the warning shown when an input lies inside another input.
+    pub fn overlapping_input(inner: &str, outer: &str, dropped: bool) -> String {
+        let consequence = if dropped {
+            "- dropped it (--dedup-inputs)"
+        } else {
+            "- its files will be bundled twice (use --dedup-inputs)"
+        };
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            format!("Input {inner} is inside {outer}").yellow(),
+            consequence.dimmed()
+        )
+    }
+
+    /// Returns the warning shown when a directory is skipped because it can't be read.
+    pub fn skipped_unreadable(path: &str) -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            format!("Skipped {path}").yellow(),
+            "- permission denied (use --strict to fail instead)".dimmed()
+        )
+    }
+
+    /// Returns the warning shown when a followed symlink leads to a directory already walked.
+    pub fn symlink_loop(path: &str, target: &str) -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            format!("Skipped {path}").yellow(),
+            format!("- symlink loop back to {target}").dimmed()
+        )
+    }
+
+    /// Returns the notice shown when `--allow-empty` lets a run without files succeed.
+    pub fn empty_bundle(output: &str) -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            "No files found in any input".yellow(),
+            format!("- wrote an empty bundle to {output}").dimmed()
+        )
+    }
+
+    /// Returns the warning shown when every bundled file ended up without any content.
+    pub fn empty_content(count: usize) -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            format!("All {count} bundled files are empty, stubbed, or skipped").yellow(),
+            "- try relaxing --stub, --max-file-size, or the other content filters".dimmed()
+        )
+    }
+
+    /// Returns the warning shown when a bundled file lies outside the relativization root.
+    pub fn outside_root(path: &str, root: &str) -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            format!("{path} is outside the root {root}").yellow(),
+            "- its header will show the full path (check --root)".dimmed()
+        )
+    }
+
     // -------------------- Action Messages --------------------
 
     /// Returns the copying to clipboard message.
@@ -61,6 +157,35 @@ impl Messages {
         )
     }
 
+    /// Returns the message shown once `--stats-to-clipboard` copied the statistics.
+    pub fn stats_copied() -> String {
+        format!(
+            "{} {}",
+            "📋".green(),
+            "Stats copied! Paste the summary anywhere~".bright_green()
+        )
+    }
+
+    /// Returns a notice that a clipboard MIME hint can't be honored on this platform.
+    pub fn clipboard_mime_fallback(hint: &str) -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            format!("Clipboard type '{hint}' isn't supported here").yellow(),
+            "- falling back to text/plain".dimmed()
+        )
+    }
+
+    /// Returns the notice shown when `--compress-clipboard` is used without the OSC 52 backend.
+    pub fn compress_clipboard_ignored() -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            "--compress-clipboard only applies to the osc52 backend".yellow(),
+            "- copying uncompressed".dimmed()
+        )
+    }
+
     /// Returns the opening editor message.
     pub fn opening_editor() -> String {
         "✏️  Opening your treasure chest..."
@@ -89,6 +214,35 @@ impl Messages {
             .to_string()
     }
 
+    /// Returns the summary shown after `unbundle` recreated a bundle's files.
+    pub fn unbundled(count: usize, dir: &str) -> String {
+        format!(
+            "{} {}",
+            "📦".green(),
+            format!("Unpacked {count} files into {dir}").bright_green()
+        )
+    }
+
+    /// Returns the summary closing the `--dry-run` file list.
+    pub fn dry_run_summary(count: usize, bytes: &str) -> String {
+        format!(
+            "{} {} {}",
+            "🔍".cyan(),
+            format!("{count} files would be bundled").bright_cyan(),
+            format!("(~{bytes}, nothing written)").dimmed()
+        )
+    }
+
+    /// Returns the notice shown when `unbundle` meets a binary file bundled without content.
+    pub fn unbundle_skipped(path: &str) -> String {
+        format!(
+            "{} {} {}",
+            "⚠️".yellow(),
+            format!("Skipped {path}").yellow(),
+            "- its content wasn't bundled (use run --base64)".dimmed()
+        )
+    }
+
     /// Returns the showing stats message.
     pub fn showing_stats() -> String {
         "📊 Let's see what we've collected!"
@@ -97,6 +251,14 @@ impl Messages {
             .to_string()
     }
 
+    /// Returns the heading above the `--preview` lines.
+    pub fn preview_heading(lines: usize) -> String {
+        format!("👀 First {lines} lines of the bundle:")
+            .bright_magenta()
+            .bold()
+            .to_string()
+    }
+
     /// Returns the ready to launch message.
     pub fn ready_to_launch() -> String {
         format!(
@@ -119,9 +281,9 @@ impl Messages {
         )
     }
 
-    /// Returns the applying ignore rules message.
-    pub fn applying_ignore_rules() -> String {
-        "  📝 Applying rules from .treeclipignore"
+    /// Returns the applying ignore rules message for the given ignore file name.
+    pub fn applying_ignore_rules(file_name: &str) -> String {
+        format!("  📝 Applying rules from {file_name}")
             .dimmed()
             .to_string()
     }
@@ -137,6 +299,18 @@ mod messages_tests {
         assert!(!Messages::scanning_files().is_empty());
     }
 
+    #[test]
+    fn test_skipped_long_lines_message() {
+        let message = Messages::skipped_long_lines("dist/app.min.js", 5000);
+        assert!(message.contains("dist/app.min.js"));
+        assert!(message.contains("5000"));
+        assert!(Messages::skipped_too_many_lines("logs/app.log", 10000).contains("10000"));
+        assert!(Messages::skipped_unreadable("/tmp/project/locked").contains("permission denied"));
+        assert!(Messages::not_a_git_repo("/tmp/project", "--git-meta").contains("/tmp/project"));
+        assert!(Messages::overlapping_input("src", ".", true).contains("--dedup-inputs"));
+        assert!(Messages::outside_root("/tmp/other/a.rs", "/tmp/project").contains("/tmp/project"));
+    }
+
     #[test]
     fn test_progress_messages_not_empty() {
         assert!(!Messages::traversing_tree().is_empty());
@@ -148,6 +322,8 @@ mod messages_tests {
         assert!(!Messages::copying_clipboard().is_empty());
         assert!(!Messages::clipboard_ready().is_empty());
         assert!(!Messages::clipboard_skipped().is_empty());
+        assert!(Messages::clipboard_mime_fallback("text/markdown").contains("text/markdown"));
+        assert!(Messages::compress_clipboard_ignored().contains("osc52"));
         assert!(!Messages::opening_editor().is_empty());
         assert!(!Messages::editor_opened().is_empty());
         assert!(!Messages::cleaning_up().is_empty());
@@ -163,7 +339,7 @@ mod messages_tests {
         assert!(message.contains(path));
         assert!(!message.is_empty());
 
-        assert!(!Messages::applying_ignore_rules().is_empty());
+        assert!(Messages::applying_ignore_rules(".treeclipignore").contains(".treeclipignore"));
     }
 
     #[test]
@@ -1,5 +1,9 @@
 pub mod animations;
 pub mod banner;
+pub mod console;
 pub mod formatter;
+pub mod highlight;
 pub mod messages;
+pub mod progress;
 pub mod table;
+pub mod tree;
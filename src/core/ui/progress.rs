@@ -0,0 +1,327 @@
+//! progress - Renders per-file progress while bundling, in a user-selectable style.
+//!
+//! The bar with an accurate total lives here as the `bar` style of [`ProgressReporter`]
+//! rather than as a separate `ProgressBar` in `animations`: the traversal collects every
+//! file before writing any, so one reporter serves all styles from the same count.
+
+use crate::core::ui::{animations, console};
+use clap::ValueEnum;
+use colored::Colorize;
+use indicatif::{
+    ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle as BarStyle, TermLike,
+};
+use std::{fmt, io};
+
+/// Width of the `bar` style, in cells.
+const BAR_WIDTH: usize = 30;
+
+/// Width reported to indicatif; the bar itself is [`BAR_WIDTH`] cells.
+const TERM_WIDTH: u16 = 80;
+
+/// How often indicatif redraws the `bar` and `spinner` styles, per second.
+const REFRESH_HZ: u8 = 20;
+
+/// Number of files between updates for the `dots` and `emoji` styles.
+const TICK_INTERVAL: usize = 5;
+
+/// Emojis cycled through by the `emoji` style.
+const TREE_EMOJIS: [&str; 6] = ["🌱", "🌿", "🍃", "🌳", "🌲", "🎄"];
+
+/// Frames cycled through by the `spinner` style.
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// How progress is displayed while files are being bundled.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressStyle {
+    /// A dot for every few files
+    Dots,
+    /// A bar filling up towards the total file count
+    Bar,
+    /// A spinner with a running file count
+    Spinner,
+    /// The cute tree emoji counter
+    Emoji,
+    /// No progress output at all
+    None,
+}
+
+impl ProgressStyle {
+    /// Resolves the effective style: an explicit choice always wins; otherwise progress
+    /// reaching a terminal is shown as the emoji counter, or as the bar (files are counted
+    /// before any is written, so the total is known) in verbose runs. Nothing is shown in
+    /// fast mode or when progress doesn't reach a terminal (quiet runs, redirected stderr).
+    pub fn resolve(
+        explicit: Option<Self>,
+        interactive: bool,
+        verbose: bool,
+        fast_mode: bool,
+    ) -> Self {
+        match explicit {
+            Some(style) => style,
+            None if !interactive || fast_mode => Self::None,
+            None if verbose => Self::Bar,
+            None => Self::Emoji,
+        }
+    }
+}
+
+/// Draws progress updates for a known number of files, on stderr.
+pub struct ProgressReporter {
+    style: ProgressStyle,
+    /// The indicatif bar behind the `bar` and `spinner` styles.
+    bar: Option<ProgressBar>,
+    drawn: bool,
+}
+
+impl ProgressReporter {
+    /// Creates a reporter for `total` files.
+    pub fn new(style: ProgressStyle, total: usize) -> Self {
+        // Colors follow `colored`, which --no-color and NO_COLOR switch off
+        let colored = colored::control::SHOULD_COLORIZE.should_colorize();
+        let (bar_colors, spinner_color) = if colored {
+            (".green/dim", ".cyan")
+        } else {
+            ("", "")
+        };
+        let bar = match style {
+            ProgressStyle::Bar => Some(indicatif_bar(
+                total,
+                &format!("[{{bar:{BAR_WIDTH}{bar_colors}}}] {{pos}}/{{len}}"),
+            )),
+            ProgressStyle::Spinner => Some(indicatif_bar(
+                total,
+                &format!("{{spinner{spinner_color}}} {{pos}} {{files}}"),
+            )),
+            _ => None,
+        };
+        Self {
+            style,
+            bar,
+            drawn: false,
+        }
+    }
+
+    /// Reports that `done` files have been processed so far.
+    pub fn tick(&mut self, done: usize) {
+        if let Some(bar) = &self.bar {
+            bar.set_position(done as u64);
+            self.drawn = true;
+            return;
+        }
+        let Some(update) = self.render(done) else {
+            return;
+        };
+        console::progress(update);
+        self.drawn = true;
+    }
+
+    /// Ends the progress line, if anything was drawn.
+    pub fn finish(&mut self) {
+        if self.drawn {
+            // Leaves the bar at the files actually processed, e.g. when a limit cut the run short
+            if let Some(bar) = &self.bar {
+                bar.abandon();
+            }
+            console::progress("\n");
+            self.drawn = false;
+        }
+    }
+}
+
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+impl ProgressReporter {
+    /// Renders the update for `done` files of the hand-drawn styles, or `None` if nothing
+    /// should be drawn.
+    fn render(&self, done: usize) -> Option<String> {
+        match self.style {
+            ProgressStyle::Dots => done
+                .is_multiple_of(TICK_INTERVAL)
+                .then(|| ".".bright_yellow().to_string()),
+            ProgressStyle::Emoji => animations::progress_counter(&TREE_EMOJIS, done, TICK_INTERVAL)
+                .map(|message| format!("\r{message}")),
+            ProgressStyle::Bar | ProgressStyle::Spinner | ProgressStyle::None => None,
+        }
+    }
+}
+
+/// Creates an indicatif bar for `total` files, drawn with `template` through [`ConsoleTerm`].
+fn indicatif_bar(total: usize, template: &str) -> ProgressBar {
+    let style = BarStyle::with_template(template)
+        .expect("valid progress template")
+        .progress_chars("█░")
+        .tick_strings(&SPINNER_FRAMES)
+        .with_key(
+            "files",
+            |state: &ProgressState, out: &mut dyn fmt::Write| {
+                let _ = out.write_str(if state.pos() == 1 { "file" } else { "files" });
+            },
+        );
+    let target = ProgressDrawTarget::term_like_with_hz(Box::new(ConsoleTerm), REFRESH_HZ);
+    ProgressBar::with_draw_target(Some(total as u64), target).with_style(style)
+}
+
+/// Moves the cursor `n` cells in the direction of the CSI `code` (`A` up, `B` down, `C`
+/// right, `D` left); a zero move is skipped, as terminals treat it as a move by one.
+fn move_cursor(n: usize, code: char) {
+    if n > 0 {
+        console::progress(format!("\x1b[{n}{code}"));
+    }
+}
+
+/// A terminal for indicatif that writes through [`console::progress`], so bars go to
+/// stderr and respect `--quiet` like every other progress output.
+#[derive(Debug)]
+struct ConsoleTerm;
+
+impl TermLike for ConsoleTerm {
+    fn width(&self) -> u16 {
+        TERM_WIDTH
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        move_cursor(n, 'A');
+        Ok(())
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        move_cursor(n, 'B');
+        Ok(())
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        move_cursor(n, 'C');
+        Ok(())
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        move_cursor(n, 'D');
+        Ok(())
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        console::progress(format!("{s}\n"));
+        Ok(())
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        console::progress(s);
+        Ok(())
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        console::progress("\r\x1b[2K");
+        Ok(())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod progress_tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_defaults() {
+        assert_eq!(
+            ProgressStyle::resolve(None, true, false, false),
+            ProgressStyle::Emoji
+        );
+        assert_eq!(
+            ProgressStyle::resolve(None, true, true, false),
+            ProgressStyle::Bar
+        );
+        assert_eq!(
+            ProgressStyle::resolve(None, true, true, true),
+            ProgressStyle::None
+        );
+        assert_eq!(
+            ProgressStyle::resolve(None, false, true, false),
+            ProgressStyle::None
+        );
+        assert_eq!(
+            ProgressStyle::resolve(Some(ProgressStyle::Bar), false, false, true),
+            ProgressStyle::Bar
+        );
+    }
+
+    /// Runs a reporter for `total` files up to `done`, returning its last drawn line.
+    fn final_line(style: ProgressStyle, total: usize, done: usize) -> String {
+        let mut reporter = ProgressReporter::new(style, total);
+        let ((), captured) = console::capture::run(|| {
+            for done in 0..=done {
+                reporter.tick(done);
+            }
+            reporter.finish();
+        });
+        let last = captured.stderr.trim_end().rsplit(['\r', '\n']).next();
+        last.unwrap_or_default().replace("\x1b[2K", "")
+    }
+
+    #[test]
+    fn test_bar_fills_to_total() {
+        let half = final_line(ProgressStyle::Bar, 4, 2);
+        let full = final_line(ProgressStyle::Bar, 4, 4);
+        assert!(half.contains("2/4"));
+        assert!(full.contains("4/4"));
+        assert!(!full.contains('░'));
+    }
+
+    #[test]
+    fn test_bar_render_at_percentages() {
+        let cells = |done| {
+            let line = final_line(ProgressStyle::Bar, 1200, done);
+            (line.matches('█').count(), line.matches('░').count())
+        };
+        assert_eq!(cells(0), (0, BAR_WIDTH));
+        assert_eq!(cells(340), (8, BAR_WIDTH - 8));
+        assert_eq!(cells(600), (BAR_WIDTH / 2, BAR_WIDTH / 2));
+        assert_eq!(cells(1200), (BAR_WIDTH, 0));
+        assert!(final_line(ProgressStyle::Bar, 1200, 340).ends_with(" 340/1200"));
+    }
+
+    #[test]
+    fn test_spinner_counts_files() {
+        assert!(final_line(ProgressStyle::Spinner, 7, 7).ends_with(" 7 files"));
+        assert!(final_line(ProgressStyle::Spinner, 1, 1).ends_with(" 1 file"));
+    }
+
+    #[test]
+    fn test_bar_respects_quiet() {
+        let mut reporter = ProgressReporter::new(ProgressStyle::Bar, 2);
+        let ((), captured) = console::capture::run(|| {
+            console::set_quiet(true);
+            reporter.tick(1);
+            reporter.tick(2);
+            reporter.finish();
+        });
+        assert!(captured.stderr.is_empty());
+    }
+
+    #[test]
+    fn test_progress_goes_to_stderr() {
+        let mut reporter = ProgressReporter::new(ProgressStyle::Bar, 2);
+        let ((), captured) = console::capture::run(|| {
+            reporter.tick(1);
+            reporter.tick(2);
+            reporter.finish();
+        });
+        assert!(captured.stdout.is_empty());
+        assert!(captured.stderr.contains("2/2"));
+        assert!(captured.stderr.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_none_renders_nothing() {
+        let mut reporter = ProgressReporter::new(ProgressStyle::None, 10);
+        let ((), captured) = console::capture::run(|| {
+            for done in 1..=10 {
+                reporter.tick(done);
+            }
+            reporter.finish();
+        });
+        assert!(captured.stdout.is_empty() && captured.stderr.is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+//! tree - Renders file paths as an ASCII directory tree, like the `tree` command.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A directory level of the tree; entries without children are files, unless marked empty.
+#[derive(Default)]
+struct TreeNode {
+    /// An empty directory (`--tree-show-empty-dirs`), shown with a trailing `/`.
+    empty_dir: bool,
+    /// Children in the order they were first seen.
+    children: Vec<(String, TreeNode)>,
+    /// Position of each child in `children`, by name.
+    index: HashMap<String, usize>,
+}
+
+impl TreeNode {
+    /// Returns the child called `name`, appending it if it's new.
+    fn child(&mut self, name: String) -> &mut TreeNode {
+        let position = match self.index.get(&name) {
+            Some(&position) => position,
+            None => {
+                self.index.insert(name.clone(), self.children.len());
+                self.children.push((name, TreeNode::default()));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[position].1
+    }
+}
+
+/// Renders `paths` (relative, e.g. `src/main.rs`) as a tree rooted at `.`.
+///
+/// Entries keep the order of `paths` at every level (a directory sits where its first
+/// file does), so the tree matches the bundle's `--sort` order. Directories are derived
+/// from the paths, so only directories holding at least one listed file appear, plus
+/// the `empty_dirs`, which follow the files at their level.
+pub fn render_tree<'a>(
+    paths: impl IntoIterator<Item = &'a Path>,
+    empty_dirs: impl IntoIterator<Item = &'a Path>,
+) -> String {
+    let mut root = TreeNode::default();
+    for path in paths {
+        insert(&mut root, path);
+    }
+    for dir in empty_dirs {
+        insert(&mut root, dir).empty_dir = true;
+    }
+
+    let mut rendered = String::from(".\n");
+    render_children(&root, "", &mut rendered);
+    rendered
+}
+
+/// Adds the nodes along `path` below `root`; returns the last one.
+fn insert<'t>(root: &'t mut TreeNode, path: &Path) -> &'t mut TreeNode {
+    let mut node = root;
+    for component in path.components() {
+        node = node.child(component.as_os_str().to_string_lossy().into_owned());
+    }
+    node
+}
+
+/// Appends one line per child of `node`, recursing into directories.
+fn render_children(node: &TreeNode, prefix: &str, rendered: &mut String) {
+    let count = node.children.len();
+    for (index, (name, child)) in node.children.iter().enumerate() {
+        let last = index + 1 == count;
+        let (connector, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let slash = if child.empty_dir { "/" } else { "" };
+        rendered.push_str(&format!("{prefix}{connector}{name}{slash}\n"));
+        render_children(child, &format!("{prefix}{indent}"), rendered);
+    }
+}
+
+#[cfg(test)]
+mod tree_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_tree_nests_in_given_order() {
+        let paths = [
+            "Cargo.toml",
+            "src/core/lang.rs",
+            "src/core/mod.rs",
+            "src/main.rs",
+        ];
+        let tree = render_tree(paths.iter().map(Path::new), []);
+
+        assert_eq!(
+            tree,
+            ".\n\
+             ├── Cargo.toml\n\
+             └── src\n    \
+                 ├── core\n    \
+                 │   ├── lang.rs\n    \
+                 │   └── mod.rs\n    \
+                 └── main.rs\n"
+        );
+
+        // Size order: a directory sits where its first file does
+        let paths = ["src/main.rs", "Cargo.toml", "src/lib.rs"];
+        assert_eq!(
+            render_tree(paths.iter().map(Path::new), []),
+            ".\n\
+             ├── src\n\
+             │   ├── main.rs\n\
+             │   └── lib.rs\n\
+             └── Cargo.toml\n"
+        );
+    }
+
+    #[test]
+    fn test_render_tree_marks_empty_dirs() {
+        let paths = ["src/main.rs", "README.md"];
+        let empty = ["src/empty", "docs"];
+        assert_eq!(
+            render_tree(paths.iter().map(Path::new), empty.iter().map(Path::new)),
+            ".\n\
+             ├── src\n\
+             │   ├── main.rs\n\
+             │   └── empty/\n\
+             ├── README.md\n\
+             └── docs/\n"
+        );
+    }
+}
@@ -2,7 +2,11 @@
 
 use crate::core::errors::FileSystemError;
 use anyhow::Context;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Validates that a path exists on the filesystem.
 ///
@@ -34,7 +38,7 @@ pub fn format_number(n: i64) -> String {
 
     let mut result = String::new();
     for (i, char) in s.chars().enumerate() {
-        if i > 0 && ((s.len() - i) % 3 == 0) {
+        if i > 0 && (s.len() - i).is_multiple_of(3) {
             result.push(',');
         }
         result.push(char);
@@ -74,20 +78,162 @@ pub fn format_bytes(bytes: usize) -> String {
     }
 }
 
+/// How many times a path is canonicalized before giving up.
+///
+/// On networked or synced filesystems resolving a just-written file can briefly time out
+/// or be interrupted.
+const CANONICALIZE_ATTEMPTS: u32 = 4;
+
+/// Pause between canonicalization attempts.
+const CANONICALIZE_RETRY_DELAY: Duration = Duration::from_millis(25);
+
 /// Canonicalizes a path and provides context on failure.
 ///
+/// Transient failures are retried a few times (see [`CANONICALIZE_ATTEMPTS`]). A
+/// `NotFound` fails right away: callers probe paths that may not exist yet (like
+/// the output before the first run), and waiting for them would only slow that down.
+///
 /// # Errors
 ///
 /// Returns `FileSystemError::CanonicalizeFailed` if canonicalization fails.
-pub fn canonicalize_path(path: &Path) -> anyhow::Result<std::path::PathBuf> {
-    path.canonicalize()
-        .map_err(|e| FileSystemError::CanonicalizeFailed {
-            path: path.to_path_buf(),
-            source: e,
-        })
-        .with_context(|| format!("Failed to resolve absolute path for: {}", path.display()))
+pub fn canonicalize_path(path: &Path) -> anyhow::Result<PathBuf> {
+    canonicalize_with_retry(path, Path::canonicalize)
+}
+
+/// Estimates how many LLM tokens `text` costs.
+///
+/// Averages two rules of thumb: ~4 characters per token, and ~3/4 of a word per
+/// token. The character rule undercounts prose full of short words, the word rule
+/// undercounts code with long identifiers; together they track real tokenizers better.
+pub fn estimate_tokens(text: &str) -> usize {
+    token_estimate(text.chars().count(), text.split_whitespace().count())
+}
+
+/// [`estimate_tokens`] from already counted characters and whitespace-separated words.
+pub fn token_estimate(chars: usize, words: usize) -> usize {
+    (chars.div_ceil(4) + (words * 4).div_ceil(3)).div_ceil(2)
+}
+
+/// Runs `f` on a worker thread and waits at most `timeout` for its result.
+///
+/// Returns `None` if the deadline passes first. The worker is then detached and
+/// keeps running until it finishes or the process exits.
+pub fn run_with_timeout<T, F>(timeout: Duration, f: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if we already timed out; nothing to report then
+        let _ = sender.send(f());
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+/// Returns today's date in UTC as `YYYY-MM-DD`.
+pub fn today_utc() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Returns the instant of a UTC calendar date plus `seconds_of_day`, or `None` for a
+/// date that doesn't exist (e.g. February 30th).
+pub fn utc_instant(year: i64, month: u32, day: u32, seconds_of_day: u64) -> Option<SystemTime> {
+    let days = days_from_civil(year, month, day);
+    if civil_from_days(days) != (year, month, day) || seconds_of_day >= 86_400 {
+        return None;
+    }
+    let seconds = days
+        .checked_mul(86_400)?
+        .checked_add(seconds_of_day as i64)?;
+    let offset = Duration::from_secs(seconds.unsigned_abs());
+    if seconds >= 0 {
+        UNIX_EPOCH.checked_add(offset)
+    } else {
+        UNIX_EPOCH.checked_sub(offset)
+    }
 }
 
+// -------------------------------------------- Private Helper Functions --------------------------------------------
+
+/// Converts a proleptic Gregorian date into days since the Unix epoch; the inverse of
+/// [`civil_from_days`] (out-of-range months and days spill over).
+///
+/// Based on Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Converts days since the Unix epoch into a proleptic Gregorian `(year, month, day)`.
+///
+/// Based on Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Canonicalizes `path` with `probe`, retrying failures that may clear up on their own.
+fn canonicalize_with_retry(
+    path: &Path,
+    mut probe: impl FnMut(&Path) -> io::Result<PathBuf>,
+) -> anyhow::Result<PathBuf> {
+    let mut attempt = 1;
+    loop {
+        match probe(path) {
+            Ok(canonical) => return Ok(canonical),
+            Err(e) if attempt < CANONICALIZE_ATTEMPTS && is_transient(&e) => {
+                attempt += 1;
+                thread::sleep(CANONICALIZE_RETRY_DELAY);
+            }
+            Err(e) => {
+                return Err(FileSystemError::CanonicalizeFailed {
+                    path: path.to_path_buf(),
+                    source: e,
+                })
+                .with_context(|| {
+                    format!("Failed to resolve absolute path for: {}", path.display())
+                });
+            }
+        }
+    }
+}
+
+/// Checks whether a canonicalization error is worth retrying.
+fn is_transient(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+    )
+}
+
+/// Serializes tests that change the process-wide working directory.
+#[cfg(test)]
+pub static CWD_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
 #[cfg(test)]
 mod utils_tests {
     use super::*;
@@ -192,6 +338,66 @@ mod utils_tests {
         );
     }
 
+    #[test]
+    fn test_canonicalize_retries_until_path_resolves() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let fresh = temp_dir.path().join("bundle.txt");
+
+        // The first two probes time out on a file that hasn't settled yet
+        let mut probes = 0;
+        let canonical = canonicalize_with_retry(&fresh, |path| {
+            probes += 1;
+            if probes < 3 {
+                return Err(io::Error::from(io::ErrorKind::TimedOut));
+            }
+            Ok(path.to_path_buf())
+        })?;
+        assert_eq!(canonical, fresh);
+        assert_eq!(probes, 3);
+
+        // A missing path is expected (e.g. the output before the first run): no retries
+        let mut probes = 0;
+        let result = canonicalize_with_retry(&fresh, |_| {
+            probes += 1;
+            Err(io::Error::from(io::ErrorKind::NotFound))
+        });
+        assert!(result.is_err());
+        assert_eq!(probes, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("   \n"), 1);
+        assert_eq!(estimate_tokens("abcd"), 2);
+        assert_eq!(estimate_tokens(&"✨".repeat(8)), 2);
+        // Prose: many short words
+        assert_eq!(
+            estimate_tokens("The quick brown fox jumps over the lazy dog."),
+            12
+        );
+        // Code: few, long, symbol-heavy words
+        assert_eq!(
+            estimate_tokens("fn main() { println!(\"Hello, world!\"); }"),
+            9
+        );
+        assert_eq!(
+            estimate_tokens("pub fn estimate_tokens(text: &str) -> usize"),
+            10
+        );
+    }
+
+    #[test]
+    fn test_token_estimate_matches_counted_text() {
+        let text = "==> src/lib.rs\npub mod core;\n";
+        assert_eq!(
+            token_estimate(text.chars().count(), 5),
+            estimate_tokens(text)
+        );
+    }
+
     #[test]
     fn test_validate_path_provides_context() {
         let nonexistent = Path::new("/this/path/does/not/exist");
@@ -204,4 +410,45 @@ mod utils_tests {
         // Should contain the path in error
         assert!(error_chain.contains("does/not/exist"));
     }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn test_utc_instant() {
+        let seconds = |instant: SystemTime| instant.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(utc_instant(1970, 1, 1, 0).map(seconds), Some(0));
+        assert_eq!(
+            utc_instant(2024, 1, 1, 3_600).map(seconds),
+            Some(19_723 * 86_400 + 3_600)
+        );
+        assert_eq!(days_from_civil(2000, 2, 29), 11_016);
+        assert_eq!(utc_instant(2023, 2, 29, 0), None);
+        assert_eq!(utc_instant(2024, 13, 1, 0), None);
+        assert!(utc_instant(1969, 12, 31, 0).is_some());
+    }
+
+    #[test]
+    fn test_today_utc_format() {
+        let today = today_utc();
+        assert_eq!(today.len(), 10);
+        assert_eq!(today.as_bytes()[4], b'-');
+        assert_eq!(today.as_bytes()[7], b'-');
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        assert_eq!(run_with_timeout(Duration::from_secs(5), || 42), Some(42));
+
+        let slow = run_with_timeout(Duration::from_millis(20), || {
+            thread::sleep(Duration::from_secs(5));
+            42
+        });
+        assert_eq!(slow, None);
+    }
 }
@@ -1,7 +1,10 @@
 //! main - Entry point for the TreeClip CLI application.
 
-use crate::commands::run;
-use clap::Parser;
+use crate::commands::{json_schema, run, stats, unbundle};
+use crate::core::config::{self, Config};
+use crate::core::ui::banner;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches};
 use cli::*;
 
 mod cli;
@@ -12,9 +15,27 @@ fn main() -> anyhow::Result<()> {
     // NOTE: Small delay for dramatic effect - consider removing in production
     std::thread::sleep(std::time::Duration::from_millis(100));
 
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let mut rng = banner::decoration_rng(cli.seed);
     match cli.command {
-        Commands::Run(run_args) => run::execute(run_args)?,
+        Commands::Run(mut run_args) => {
+            let run_matches = matches.subcommand_matches("run").expect("parsed as run");
+            let set_on_cli =
+                |field: &str| run_matches.value_source(field) == Some(ValueSource::CommandLine);
+
+            // treeclip.toml defaults fill in whatever the command line left out
+            let root = run_args.root.clone().unwrap_or_else(|| ".".into());
+            if let Some((_, config)) = Config::discover(&root)? {
+                config.apply(&mut run_args, set_on_cli)?;
+            }
+            config::check_requires(&run_args, set_on_cli)?;
+            config::check_conflicts(&run_args)?;
+            run::execute(run_args, &mut rng)?
+        }
+        Commands::Stats(stats_args) => stats::execute(stats_args)?,
+        Commands::Unbundle(unbundle_args) => unbundle::execute(unbundle_args)?,
+        Commands::JsonSchema => json_schema::execute()?,
     }
 
     Ok(())
@@ -28,6 +49,9 @@ mod main_tests {
     #[test]
     fn test_cli_parsing_does_not_panic() -> anyhow::Result<()> {
         let temp_dir = tempfile::tempdir()?;
+        let _cwd = crate::core::utils::CWD_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
         std::env::set_current_dir(&temp_dir)?;
 
         let args = vec!["treeclip", "run", "."];